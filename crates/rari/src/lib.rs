@@ -6,6 +6,6 @@ mod utils;
 pub use ::async_trait;
 pub use rendering::{
     base::{RscJsLoader, RscRenderer},
-    r#static::RscHtmlRenderer,
+    r#static::{AssetLink, RscHtmlRenderer},
 };
 pub use rsc::{ComponentRegistry, extract_dependencies};
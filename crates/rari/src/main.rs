@@ -97,8 +97,8 @@ fn cli() -> Command {
                 .short('m')
                 .long("mode")
                 .value_name("MODE")
-                .help("Server mode: development or production")
-                .value_parser(["development", "dev", "production", "prod"])
+                .help("Server mode: development, production or test")
+                .value_parser(["development", "dev", "production", "prod", "test"])
                 .default_value("development"),
         )
         .arg(
@@ -269,6 +269,7 @@ fn load_configuration(matches: &clap::ArgMatches) -> Result<Config, RariError> {
     let mode = match mode_str.as_str() {
         "development" | "dev" => Mode::Development,
         "production" | "prod" => Mode::Production,
+        "test" => Mode::Test,
         mode => return Err(RariError::configuration(format!("Invalid mode: {mode}"))),
     };
 
@@ -33,7 +33,7 @@ use crate::{
             },
             response::RouteCachePolicy,
         },
-        config::{CacheLayerConfig, Config},
+        config::{CacheLayerConfig, Config, RscConfig},
         middleware::request_context::RequestContext,
         rendering::metadata_injection::merge_streaming_head_content,
         routing::app_router::AppRouteMatch,
@@ -58,6 +58,18 @@ fn should_use_layout_html_cache(
     let cache_control = config.get_cache_control_for_route(&context.pathname);
     RouteCachePolicy::from_cache_control(cache_control, &context.pathname).enabled
 }
+
+fn stream_channel_capacity() -> usize {
+    stream_channel_capacity_for(Config::get())
+}
+
+fn stream_channel_capacity_for(config: Option<&Config>) -> usize {
+    config.map_or_else(
+        || RscConfig::default().stream_channel_capacity(),
+        |config| config.rsc.stream_channel_capacity(),
+    )
+}
+
 const JS_GET_RESULT: &str = r"
 globalThis['~rsc'].renderResult
 ";
@@ -141,11 +153,11 @@ impl LayoutHtmlCache {
         Self { handler, default_ttl_secs }
     }
 
-    fn namespaced(key: u64) -> String {
+    fn namespaced(key: &str) -> String {
         format!("{LAYOUT_KEY_PREFIX}{key}")
     }
 
-    pub async fn get(&self, key: u64) -> Option<String> {
+    pub async fn get(&self, key: &str) -> Option<String> {
         let ns_key = Self::namespaced(key);
         let bytes = match self.handler.get(&ns_key).await {
             Ok(Some(b)) => b,
@@ -164,7 +176,7 @@ impl LayoutHtmlCache {
         }
     }
 
-    pub async fn insert(&self, key: u64, html: String) -> Result<(), CacheError> {
+    pub async fn insert(&self, key: &str, html: String) -> Result<(), CacheError> {
         self.insert_with_tags(key, html, &[], None).await
     }
 
@@ -175,7 +187,7 @@ impl LayoutHtmlCache {
     /// keep the layer default.
     pub async fn insert_with_tags(
         &self,
-        key: u64,
+        key: &str,
         html: String,
         tags: &[String],
         route_max_age_secs: Option<u64>,
@@ -591,7 +603,7 @@ impl LayoutRenderer {
 
         if layout_cache_enabled
             && !return_rsc_on_fallback
-            && let Some(cached_html) = self.html_cache.get(cache_key).await
+            && let Some(cached_html) = self.html_cache.get(&cache_key).await
         {
             return Ok(RenderResult::Static(cached_html));
         }
@@ -614,7 +626,7 @@ impl LayoutRenderer {
         if return_rsc_on_fallback {
             if needs_streaming {
                 let (chunk_sender, chunk_receiver) =
-                    mpsc::channel::<Result<Vec<u8>, RariError>>(128);
+                    mpsc::channel::<Result<Vec<u8>, RariError>>(stream_channel_capacity());
 
                 let stream_id = Uuid::new_v4().to_string();
                 let composition_script = Self::build_composition_script_with_stream(
@@ -767,7 +779,7 @@ impl LayoutRenderer {
 
             if needs_streaming {
                 let (chunk_sender, chunk_receiver) =
-                    mpsc::channel::<Result<Vec<u8>, RariError>>(128);
+                    mpsc::channel::<Result<Vec<u8>, RariError>>(stream_channel_capacity());
 
                 let stream_id = Uuid::new_v4().to_string();
                 let shell = Bytes::from_static(b"<!DOCTYPE html>");
@@ -819,10 +831,16 @@ impl LayoutRenderer {
                     async move {
                         renderer.ensure_streaming_pipeline().await?;
 
-                        let html_renderer = RscHtmlRenderer::new(Arc::clone(&renderer.runtime));
+                        let html_renderer = RscHtmlRenderer::new(Arc::clone(&renderer.runtime))
+                            .with_missing_root_policy(config.rsc_html.missing_root)
+                            .with_missing_template_policy(config.rsc_html.missing_template)
+                            .with_script_placement(config.rsc_html.script_placement)
+                            .with_vite_client_entry(RscHtmlRenderer::vite_client_entry_for(
+                                &config,
+                            ));
                         let css_links = RscHtmlRenderer::css_links_for_route(&route_match);
                         let cache_template = config.rsc_html.cache_template;
-                        let is_dev_mode = config.is_development();
+                        let is_dev_mode = config.uses_source_template();
                         let template =
                             html_renderer.load_template(cache_template, is_dev_mode).await?;
                         let template = RscHtmlRenderer::inject_css_links(&template, &css_links);
@@ -957,11 +975,18 @@ impl LayoutRenderer {
                         renderer.ensure_streaming_pipeline().await?;
 
                         let runtime = Arc::clone(&renderer.runtime);
-                        let html_renderer =
-                            Arc::new(RscHtmlRenderer::new(Arc::clone(&renderer.runtime)));
+                        let html_renderer = Arc::new(
+                            RscHtmlRenderer::new(Arc::clone(&renderer.runtime))
+                                .with_missing_root_policy(config.rsc_html.missing_root)
+                                .with_missing_template_policy(config.rsc_html.missing_template)
+                                .with_script_placement(config.rsc_html.script_placement)
+                                .with_vite_client_entry(RscHtmlRenderer::vite_client_entry_for(
+                                    &config,
+                                )),
+                        );
                         let css_links = RscHtmlRenderer::css_links_for_route(&route_match);
                         let cache_template = config.rsc_html.cache_template;
-                        let is_dev_mode = config.is_development();
+                        let is_dev_mode = config.uses_source_template();
                         let template =
                             html_renderer.load_template(cache_template, is_dev_mode).await?;
                         let template = RscHtmlRenderer::inject_css_links(&template, &css_links);
@@ -1015,6 +1040,9 @@ impl LayoutRenderer {
 
                             return {{ ok: true, html, isDynamic, pageCacheTags }};
                         }} catch(e) {{
+                            if (e?.redirect) {{
+                                return {{ ok: false, redirect: e.redirect }};
+                            }}
                             return {{ ok: false, error: String(e?.message || e) }};
                         }}
                     }})()",
@@ -1050,13 +1078,7 @@ impl LayoutRenderer {
 
                             let ok = result.get("ok").and_then(Value::as_bool).unwrap_or(false);
                             if !ok {
-                                let err = result
-                                    .get("error")
-                                    .and_then(|v| v.as_str())
-                                    .unwrap_or("unknown");
-                                return Err(RariError::internal(format!(
-                                    "Static document render failed: {err}"
-                                )));
+                                return Err(static_render_error(&result));
                             }
 
                             let html = result
@@ -1117,7 +1139,7 @@ impl LayoutRenderer {
                 });
                 let _ = self
                     .html_cache
-                    .insert_with_tags(cache_key, html.clone(), &layout_cache_tags, route_max_age)
+                    .insert_with_tags(&cache_key, html.clone(), &layout_cache_tags, route_max_age)
                     .await;
             }
 
@@ -1160,7 +1182,10 @@ impl LayoutRenderer {
         let promise_result =
             rt.execute_script("compose_and_render".to_string(), composition_script).await?;
 
-        if promise_result.is_object() && promise_result.get("rsc_data").is_some() {
+        if promise_result.is_object()
+            && (promise_result.get("rsc_data").is_some()
+                || promise_result.get("redirect").is_some())
+        {
             Ok(promise_result)
         } else {
             rt.execute_script("get_result".to_string(), JS_GET_RESULT.to_string()).await
@@ -1181,6 +1206,10 @@ impl LayoutRenderer {
     }
 
     fn extract_flight_text(result: &Value) -> Result<String, RariError> {
+        if let Some(redirect) = render_redirect(result) {
+            return Err(redirect);
+        }
+
         let rsc_data = result.get("rsc_data").ok_or_else(|| {
             tracing::error!(
                 "Failed to extract RSC data from result (keys: {:?})",
@@ -1455,48 +1484,105 @@ impl LayoutRenderer {
     }
 }
 
+/// A `redirect` payload thrown by a component (a location string, or
+/// `{ destination, status }`) as a [`RariError::Redirect`], so the route
+/// handler can answer with a 3xx.
+fn render_redirect(result: &Value) -> Option<RariError> {
+    let (location, status) = match result.get("redirect")? {
+        Value::String(location) => (location.as_str(), None),
+        Value::Object(redirect) => (
+            redirect.get("destination").and_then(Value::as_str)?,
+            redirect.get("status").and_then(Value::as_u64),
+        ),
+        _ => return None,
+    };
+
+    let status = status.and_then(|status| u16::try_from(status).ok()).unwrap_or(307);
+    Some(RariError::redirect(location, status))
+}
+
+/// Error for a failed static document render; see [`render_redirect`].
+fn static_render_error(result: &Value) -> RariError {
+    if let Some(redirect) = render_redirect(result) {
+        return redirect;
+    }
+
+    let err = result.get("error").and_then(|v| v.as_str()).unwrap_or("unknown");
+    RariError::internal(format!("Static document render failed: {err}"))
+}
+
 #[cfg(test)]
 #[expect(clippy::expect_used)]
 mod tests {
     use super::*;
-    use crate::server::cache::handler::NoOpCacheHandler;
+    use crate::server::{cache::handler::NoOpCacheHandler, config::Mode};
+
+    #[test]
+    fn test_static_render_redirect_is_typed() {
+        let result = serde_json::json!({
+            "ok": false,
+            "redirect": { "destination": "/login", "status": 303 }
+        });
+        assert_eq!(static_render_error(&result).redirect_target(), Some(("/login", 303)));
+
+        let result = serde_json::json!({ "ok": false, "redirect": "/signin" });
+        assert_eq!(static_render_error(&result).redirect_target(), Some(("/signin", 307)));
+
+        let result = serde_json::json!({ "ok": false, "error": "boom" });
+        let err = static_render_error(&result);
+        assert!(err.redirect_target().is_none());
+        assert!(err.to_string().contains("boom"));
+
+        let result = serde_json::json!({ "redirect": "/login" });
+        let err = LayoutRenderer::extract_flight_text(&result).expect_err("redirect");
+        assert_eq!(err.redirect_target(), Some(("/login", 307)));
+    }
+
+    #[test]
+    fn test_stream_channel_capacity_uses_config_override() {
+        assert_eq!(stream_channel_capacity_for(None), RscConfig::default().stream_buffer_size);
+
+        let mut config = Config::new(Mode::Production);
+        config.rsc.stream_buffer_size = 4;
+        assert_eq!(stream_channel_capacity_for(Some(&config)), 4);
+    }
 
     #[tokio::test]
     async fn test_layout_handler_round_trip() {
         let cache = LayoutHtmlCache::new();
         let html = "<!DOCTYPE html><html><body>hi</body></html>".to_string();
 
-        cache.insert(42, html.clone()).await.expect("insert");
-        let got = cache.get(42).await.expect("get");
+        cache.insert("42", html.clone()).await.expect("insert");
+        let got = cache.get("42").await.expect("get");
         assert_eq!(got, html);
 
-        assert!(cache.get(9999).await.is_none());
+        assert!(cache.get("9999").await.is_none());
     }
 
     #[tokio::test]
     async fn test_layout_clear() {
         let cache = LayoutHtmlCache::new();
-        cache.insert(1, "one".to_string()).await.expect("insert");
-        cache.insert(2, "two".to_string()).await.expect("insert");
-        cache.insert(3, "three".to_string()).await.expect("insert");
+        cache.insert("1", "one".to_string()).await.expect("insert");
+        cache.insert("2", "two".to_string()).await.expect("insert");
+        cache.insert("3", "three".to_string()).await.expect("insert");
 
-        assert!(cache.get(1).await.is_some());
-        assert!(cache.get(2).await.is_some());
-        assert!(cache.get(3).await.is_some());
+        assert!(cache.get("1").await.is_some());
+        assert!(cache.get("2").await.is_some());
+        assert!(cache.get("3").await.is_some());
 
         cache.clear().await.expect("clear");
 
-        assert!(cache.get(1).await.is_none());
-        assert!(cache.get(2).await.is_none());
-        assert!(cache.get(3).await.is_none());
+        assert!(cache.get("1").await.is_none());
+        assert!(cache.get("2").await.is_none());
+        assert!(cache.get("3").await.is_none());
     }
 
     #[tokio::test]
     async fn test_layout_with_noop_handler() {
         let cache = LayoutHtmlCache::with_handler(Arc::new(NoOpCacheHandler));
 
-        cache.insert(1, "x".to_string()).await.expect("insert is no-op but Ok");
-        assert!(cache.get(1).await.is_none());
+        cache.insert("1", "x".to_string()).await.expect("insert is no-op but Ok");
+        assert!(cache.get("1").await.is_none());
         cache.clear().await.expect("clear is no-op but Ok");
     }
 
@@ -1504,8 +1590,8 @@ mod tests {
     async fn test_layout_custom_ttl_passes_through() {
         let handler = Arc::new(MemoryCacheHandler::default());
         let cache = LayoutHtmlCache::with_ttl(handler, 60);
-        cache.insert(7, "alive".to_string()).await.expect("insert");
-        assert!(cache.get(7).await.is_some());
+        cache.insert("7", "alive".to_string()).await.expect("insert");
+        assert!(cache.get("7").await.is_some());
     }
 
     #[tokio::test]
@@ -1515,44 +1601,44 @@ mod tests {
 
         // max-age=0: the entry would be born expired, so the write is skipped
         // entirely -- nothing stored, nothing served.
-        cache.insert_with_tags(1, "short-lived".to_string(), &[], Some(0)).await.expect("insert");
-        assert!(cache.get(1).await.is_none());
+        cache.insert_with_tags("1", "short-lived".to_string(), &[], Some(0)).await.expect("insert");
+        assert!(cache.get("1").await.is_none());
         assert!(handler.is_empty(), "max-age=0 must not store an entry");
 
         // A max-age above the default is clamped down to it, never up.
         cache
-            .insert_with_tags(2, "default-lived".to_string(), &[], Some(999_999))
+            .insert_with_tags("2", "default-lived".to_string(), &[], Some(999_999))
             .await
             .expect("insert");
-        assert!(cache.get(2).await.is_some());
+        assert!(cache.get("2").await.is_some());
 
         // No max-age keeps the layer default.
-        cache.insert_with_tags(3, "default".to_string(), &[], None).await.expect("insert");
-        assert!(cache.get(3).await.is_some());
+        cache.insert_with_tags("3", "default".to_string(), &[], None).await.expect("insert");
+        assert!(cache.get("3").await.is_some());
     }
 
     #[tokio::test]
     async fn test_layout_invalidate_by_tag() {
         let cache = LayoutHtmlCache::new();
         cache
-            .insert_with_tags(42, "tagged".to_string(), &["products".to_string()], None)
+            .insert_with_tags("42", "tagged".to_string(), &["products".to_string()], None)
             .await
             .expect("insert");
-        assert!(cache.get(42).await.is_some());
+        assert!(cache.get("42").await.is_some());
 
         cache.invalidate_by_tag("products").await.expect("invalidate");
-        assert!(cache.get(42).await.is_none());
+        assert!(cache.get("42").await.is_none());
     }
 
     #[tokio::test]
     async fn test_layout_clear_removes_all_layout_keys() {
         let cache = LayoutHtmlCache::new();
         for i in 0..50 {
-            cache.insert(i, format!("v{i}")).await.expect("insert");
+            cache.insert(&i.to_string(), format!("v{i}")).await.expect("insert");
         }
         cache.clear().await.expect("clear");
         for i in 0..50 {
-            assert!(cache.get(i).await.is_none(), "key {i} survived clear");
+            assert!(cache.get(&i.to_string()).await.is_none(), "key {i} survived clear");
         }
     }
 }
@@ -7,8 +7,11 @@ pub use core::{LayoutHtmlCache, LayoutRenderer};
 
 pub use route_composer::{LayoutInfo, RouteComposer};
 pub use types::*;
+pub use utils::{
+    FlightRowOptions, create_layout_context, sort_flight_protocol, sort_flight_protocol_with,
+    split_flight_rows_with, write_chunked_stream,
+};
 pub(crate) use utils::{component_dist_path, create_component_id, drain_chunked_stream};
-pub use utils::{create_layout_context, sort_flight_protocol};
 
 #[cfg(test)]
 #[expect(clippy::unwrap_used)]
@@ -70,6 +73,7 @@ mod tests {
             template_navigation_id: None,
             metadata: None,
             streaming_head_extra: None,
+            client_ip: None,
         };
 
         let route_match = AppRouteMatch {
@@ -127,6 +131,7 @@ mod tests {
             template_navigation_id: None,
             metadata: None,
             streaming_head_extra: None,
+            client_ip: None,
         };
 
         let script = LayoutRenderer::build_composition_script(
@@ -174,6 +179,7 @@ mod tests {
             template_navigation_id: None,
             metadata: None,
             streaming_head_extra: None,
+            client_ip: None,
         };
 
         let script = LayoutRenderer::build_composition_script(
@@ -229,6 +235,7 @@ mod tests {
             template_navigation_id: None,
             metadata: None,
             streaming_head_extra: None,
+            client_ip: None,
         };
 
         let script =
@@ -271,6 +278,7 @@ mod tests {
             template_navigation_id: None,
             metadata: None,
             streaming_head_extra: None,
+            client_ip: None,
         };
 
         let script_ssr =
@@ -323,6 +331,7 @@ mod tests {
             template_navigation_id: None,
             metadata: None,
             streaming_head_extra: None,
+            client_ip: None,
         };
 
         let script_ssr = LayoutRenderer::build_composition_script(
@@ -385,6 +394,7 @@ mod tests {
             template_navigation_id: None,
             metadata: None,
             streaming_head_extra: None,
+            client_ip: None,
         };
 
         let script_ssr =
@@ -436,6 +446,7 @@ mod tests {
             template_navigation_id: None,
             metadata: None,
             streaming_head_extra: None,
+            client_ip: None,
         };
 
         let script_ssr =
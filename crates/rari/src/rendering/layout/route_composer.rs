@@ -226,7 +226,16 @@ impl RouteComposer {
             }
         } else {
             r"
-                let rscData = await globalThis.renderToRsc(elementToRender);
+                let rscData;
+                try {
+                    rscData = await globalThis.renderToRsc(elementToRender);
+                } catch (e) {
+                    if (e?.redirect) {
+                        globalThis['~rsc'].renderResult = { redirect: e.redirect };
+                        return { redirect: e.redirect };
+                    }
+                    throw e;
+                }
             "
             .to_string()
         };
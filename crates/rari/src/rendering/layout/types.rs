@@ -1,3 +1,5 @@
+use std::net::IpAddr;
+
 use bytes::Bytes;
 use rari_error::RariError;
 use rustc_hash::FxHashMap;
@@ -15,6 +17,8 @@ pub struct LayoutRenderContext {
     pub template_navigation_id: Option<u32>,
     pub metadata: Option<PageMetadata>,
     pub streaming_head_extra: Option<String>,
+    /// Address of the connected peer, used to decide whether forwarded headers are trusted.
+    pub client_ip: Option<IpAddr>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -1,15 +1,14 @@
-use std::{
-    collections::hash_map::DefaultHasher,
-    hash::{Hash, Hasher},
-    path::{Path, PathBuf},
-};
+use std::path::{Path, PathBuf};
 
 use bytes::Bytes;
 use cow_utils::CowUtils;
 use rari_error::RariError;
 use rustc_hash::FxHashMap;
 use serde_json::Value;
-use tokio::sync::mpsc::Receiver;
+use tokio::{
+    io::{AsyncWrite, AsyncWriteExt},
+    sync::mpsc::Receiver,
+};
 
 use super::LayoutRenderContext;
 use crate::server::{
@@ -17,34 +16,21 @@ use crate::server::{
     routing::{app_router::AppRouteMatch, types::ParamValue},
 };
 
+/// Layout HTML cache key for a route render. The page's params and search
+/// params are its props, so they go through [`crate::rsc::cache_key`] and
+/// reordered query strings share an entry.
 pub fn generate_cache_key(
     route_match: &AppRouteMatch,
     context: &LayoutRenderContext,
     cookie_header: Option<&str>,
-) -> u64 {
-    let mut hasher = DefaultHasher::new();
-
-    route_match.route.path.hash(&mut hasher);
-
-    let mut params: Vec<_> = context.params.iter().collect();
-    params.sort_by_key(|(k, _)| *k);
-    for (k, v) in params {
-        k.hash(&mut hasher);
-        v.hash(&mut hasher);
-    }
-
-    let mut search_params: Vec<_> = context.search_params.iter().collect();
-    search_params.sort_by_key(|(k, _)| *k);
-    for (k, v) in search_params {
-        k.hash(&mut hasher);
-        v.hash(&mut hasher);
-    }
-
-    if let Some(cookie_header) = cookie_header.filter(|value| !value.is_empty()) {
-        cookie_header.hash(&mut hasher);
-    }
+) -> String {
+    let props = serde_json::json!({
+        "params": context.params,
+        "searchParams": context.search_params,
+        "cookie": cookie_header.filter(|value| !value.is_empty()),
+    });
 
-    hasher.finish()
+    crate::rsc::cache_key(&route_match.route.path, &props)
 }
 
 fn normalize_route_component_path(file_path: &str) -> String {
@@ -166,6 +152,7 @@ pub fn create_layout_context(
         template_navigation_id: None,
         metadata: None,
         streaming_head_extra: None,
+        client_ip: None,
     }
 }
 
@@ -197,38 +184,230 @@ pub async fn drain_chunked_stream(
     Ok(output)
 }
 
-pub fn sort_flight_protocol(flight_protocol: &str) -> String {
-    let mut rows_with_ids: Vec<(u32, String)> = Vec::new();
-
-    for row in flight_protocol.lines() {
-        if let Some(colon_pos) = row.find(':') {
-            if let Ok(row_id) = u32::from_str_radix(&row[..colon_pos], 16) {
-                rows_with_ids.push((row_id, row.to_string()));
-            } else {
-                rows_with_ids.push((u32::MAX, row.to_string()));
+/// Writes a chunked render to `writer` as chunks arrive instead of buffering the whole
+/// document, for static export of large pages. Returns the number of bytes written.
+pub async fn write_chunked_stream<W>(
+    shell: Bytes,
+    closing: Bytes,
+    chunks: &mut Receiver<Result<Vec<u8>, RariError>>,
+    writer: &mut W,
+) -> Result<u64, RariError>
+where
+    W: AsyncWrite + Unpin,
+{
+    let mut written = 0u64;
+
+    writer
+        .write_all(&shell)
+        .await
+        .map_err(|e| RariError::io(format!("Failed to write stream shell: {e}")))?;
+    written += shell.len() as u64;
+
+    while let Some(chunk_result) = chunks.recv().await {
+        let data = chunk_result?;
+        writer
+            .write_all(&data)
+            .await
+            .map_err(|e| RariError::io(format!("Failed to write stream chunk: {e}")))?;
+        written += data.len() as u64;
+    }
+
+    writer
+        .write_all(&closing)
+        .await
+        .map_err(|e| RariError::io(format!("Failed to write stream closing: {e}")))?;
+    written += closing.len() as u64;
+
+    writer.flush().await.map_err(|e| RariError::io(format!("Failed to flush stream: {e}")))?;
+
+    Ok(written)
+}
+
+/// Byte length of a `<id>:T<hexlen>,<text>` row, whose text may contain raw
+/// newlines and has no terminator.
+fn text_row_len(row: &str) -> Option<usize> {
+    let colon_pos = row.find(':')?;
+    if colon_pos == 0 || !row[..colon_pos].bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let header = row[colon_pos + 1..].strip_prefix('T')?;
+    let comma_pos = header.find(',')?;
+    let text_len = usize::from_str_radix(&header[..comma_pos], 16).ok()?;
+    Some(colon_pos + 2 + comma_pos + 1 + text_len)
+}
+
+/// How [`split_flight_rows_with`] and [`sort_flight_protocol_with`] read a
+/// Flight payload. Library-only: the server itself always reads
+/// newline-delimited rows and no config key sets these.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FlightRowOptions {
+    /// ASCII byte ending a row, ignored inside JSON strings. Set through
+    /// [`Self::with_delimiter`] so a multi-byte UTF-8 character is never split.
+    delimiter: u8,
+    /// Error on malformed rows instead of passing them through unchanged.
+    pub strict: bool,
+}
+
+impl Default for FlightRowOptions {
+    fn default() -> Self {
+        Self { delimiter: b'\n', strict: false }
+    }
+}
+
+impl FlightRowOptions {
+    /// Use `delimiter` to end rows.
+    ///
+    /// # Errors
+    ///
+    /// Rejects non-ASCII bytes, which could fall inside a multi-byte
+    /// character, and `"` / `\`, which open strings and escapes.
+    pub fn with_delimiter(mut self, delimiter: u8) -> Result<Self, RariError> {
+        if !delimiter.is_ascii() || matches!(delimiter, b'"' | b'\\') {
+            return Err(RariError::validation(format!(
+                "Flight row delimiter {delimiter:#04x} must be an ASCII byte other than '\"' or '\\'"
+            )));
+        }
+        self.delimiter = delimiter;
+        Ok(self)
+    }
+
+    #[must_use]
+    pub fn delimiter(self) -> u8 {
+        self.delimiter
+    }
+
+    #[must_use]
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+}
+
+/// Index of the delimiter ending a row, ignoring delimiters inside JSON
+/// strings, and whether the row ended outside a string.
+fn line_row_end(row: &str, delimiter: u8) -> (usize, bool) {
+    debug_assert!(delimiter.is_ascii(), "Flight row delimiter must be ASCII");
+    let mut in_string = false;
+    let mut escaped = false;
+    for (idx, byte) in row.bytes().enumerate() {
+        match byte {
+            _ if escaped => escaped = false,
+            b'\\' if in_string => escaped = true,
+            b'"' => in_string = !in_string,
+            _ if byte == delimiter && !in_string => return (idx, true),
+            _ => {}
+        }
+    }
+    (row.len(), !in_string)
+}
+
+fn has_row_id(row: &str) -> bool {
+    row.find(':').is_some_and(|colon_pos| {
+        colon_pos > 0 && row[..colon_pos].bytes().all(|b| b.is_ascii_hexdigit())
+    })
+}
+
+fn split_flight_rows(flight_protocol: &str) -> Vec<(&str, bool)> {
+    split_flight_rows_with(flight_protocol, FlightRowOptions::default()).unwrap_or_default()
+}
+
+/// Split a Flight payload into `(row, is_text_row)` pairs. Text rows carry
+/// their own length and no delimiter. Only strict mode returns an error.
+pub fn split_flight_rows_with(
+    flight_protocol: &str,
+    options: FlightRowOptions,
+) -> Result<Vec<(&str, bool)>, RariError> {
+    let delimiter = options.delimiter;
+    let mut rows = Vec::new();
+    let mut pos = 0;
+
+    while pos < flight_protocol.len() {
+        let rest = &flight_protocol[pos..];
+        if rest.as_bytes().first() == Some(&delimiter) {
+            pos += 1;
+            continue;
+        }
+
+        if let Some(len) = text_row_len(rest) {
+            if options.strict && pos + len > flight_protocol.len() {
+                return Err(RariError::deserialization(format!(
+                    "Flight text row at byte {pos} is shorter than its declared length"
+                )));
+            }
+            let mut end = (pos + len).min(flight_protocol.len());
+            while !flight_protocol.is_char_boundary(end) {
+                end += 1;
             }
+            rows.push((&flight_protocol[pos..end], true));
+            pos = end;
         } else {
-            rows_with_ids.push((u32::MAX, row.to_string()));
+            let (len, terminated) = line_row_end(rest, delimiter);
+            let row = &rest[..len];
+            if options.strict && !has_row_id(row) {
+                return Err(RariError::deserialization(format!(
+                    "Flight row at byte {pos} has no hex id prefix"
+                )));
+            }
+            if options.strict && !terminated {
+                return Err(RariError::deserialization(format!(
+                    "Flight row at byte {pos} has an unterminated string"
+                )));
+            }
+            rows.push((row, false));
+            pos += len + 1;
         }
     }
 
-    rows_with_ids.sort_by_key(|(id, _)| *id);
+    Ok(rows)
+}
 
-    let mut sorted =
-        rows_with_ids.iter().map(|(_, row)| row.as_str()).collect::<Vec<_>>().join("\n");
+pub fn sort_flight_protocol(flight_protocol: &str) -> String {
+    sort_rows(flight_protocol, split_flight_rows(flight_protocol), '\n')
+}
+
+/// [`sort_flight_protocol`] with a custom delimiter, or strict row checks.
+pub fn sort_flight_protocol_with(
+    flight_protocol: &str,
+    options: FlightRowOptions,
+) -> Result<String, RariError> {
+    let rows = split_flight_rows_with(flight_protocol, options)?;
+    Ok(sort_rows(flight_protocol, rows, char::from(options.delimiter)))
+}
 
-    if !sorted.is_empty() && !sorted.ends_with('\n') {
-        sorted.push('\n');
+fn sort_rows(flight_protocol: &str, rows: Vec<(&str, bool)>, delimiter: char) -> String {
+    let mut rows_with_ids: Vec<(u32, &str, bool)> = Vec::new();
+
+    for (row, is_text) in rows {
+        let row_id = row
+            .find(':')
+            .and_then(|colon_pos| u32::from_str_radix(&row[..colon_pos], 16).ok())
+            .unwrap_or(u32::MAX);
+        rows_with_ids.push((row_id, row, is_text));
+    }
+
+    rows_with_ids.sort_by_key(|(id, _, _)| *id);
+
+    let mut sorted = String::with_capacity(flight_protocol.len() + 1);
+    for (_, row, is_text) in &rows_with_ids {
+        sorted.push_str(row);
+        if !is_text {
+            sorted.push(delimiter);
+        }
+    }
+
+    if !sorted.is_empty() && !sorted.ends_with(delimiter) {
+        sorted.push(delimiter);
     }
 
-    let has_row_0 = rows_with_ids.iter().any(|(id, row)| *id == 0 && row.starts_with("0:"));
+    let has_row_0 = rows_with_ids.iter().any(|(id, row, _)| *id == 0 && row.starts_with("0:"));
 
     if !has_row_0
-        && let Some((max_id, _)) =
-            rows_with_ids.iter().filter(|(id, _)| *id != u32::MAX).max_by_key(|(id, _)| *id)
+        && let Some((max_id, _, _)) =
+            rows_with_ids.iter().filter(|(id, _, _)| *id != u32::MAX).max_by_key(|(id, _, _)| *id)
         && *max_id > 0
     {
-        let row_0 = format!("0:\"${max_id:x}\"\n");
+        let row_0 = format!("0:\"${max_id:x}\"{delimiter}");
         sorted.insert_str(0, &row_0);
     }
 
@@ -236,8 +415,9 @@ pub fn sort_flight_protocol(flight_protocol: &str) -> String {
 }
 
 #[cfg(test)]
+#[expect(clippy::expect_used)]
 mod flight_tests {
-    use super::sort_flight_protocol;
+    use super::{FlightRowOptions, sort_flight_protocol, sort_flight_protocol_with};
 
     #[test]
     fn test_sort_flight_protocol_orders_rows() {
@@ -253,8 +433,167 @@ mod flight_tests {
         assert_eq!(sorted, "0:\"$2\"\n1:\"a\"\n2:\"b\"\n");
     }
 
+    #[test]
+    fn test_sort_flight_protocol_keeps_newlines_inside_json_strings() {
+        let input = "2:{\"text\":\"line one\nline two\"}\n1:\"a\"";
+        let sorted = sort_flight_protocol(input);
+        assert_eq!(sorted, "0:\"$2\"\n1:\"a\"\n2:{\"text\":\"line one\nline two\"}\n");
+    }
+
+    #[test]
+    fn test_sort_flight_protocol_keeps_text_rows_intact() {
+        let input = "2:[\"$\",\"p\",null,{\"children\":\"$1\"}]\n1:Tb,hello\nworld3:\"c\"\n";
+        let sorted = sort_flight_protocol(input);
+        assert_eq!(
+            sorted,
+            "0:\"$3\"\n1:Tb,hello\nworld2:[\"$\",\"p\",null,{\"children\":\"$1\"}]\n3:\"c\"\n"
+        );
+    }
+
     #[test]
     fn test_sort_flight_protocol_empty() {
         assert_eq!(sort_flight_protocol(""), "");
     }
+
+    #[test]
+    fn test_sort_flight_protocol_custom_delimiter() {
+        let options = FlightRowOptions::default().with_delimiter(b'\x1e').expect("ASCII delimiter");
+        let input = "2:{\"text\":\"a\x1eb\"}\x1e1:\"a\"";
+        let sorted = sort_flight_protocol_with(input, options).expect("valid rows");
+        assert_eq!(sorted, "0:\"$2\"\x1e1:\"a\"\x1e2:{\"text\":\"a\x1eb\"}\x1e");
+    }
+
+    #[test]
+    fn test_flight_row_options_rejects_non_ascii_delimiter() {
+        assert!(FlightRowOptions::default().with_delimiter(0xc3).is_err());
+        assert!(FlightRowOptions::default().with_delimiter(b'"').is_err());
+        assert_eq!(
+            FlightRowOptions::default().with_delimiter(b';').ok().map(FlightRowOptions::delimiter),
+            Some(b';')
+        );
+    }
+
+    #[test]
+    fn test_sort_flight_protocol_strict_rejects_malformed_rows() {
+        let strict = FlightRowOptions::default().strict();
+
+        assert!(sort_flight_protocol_with("1:\"a\"\n2:Tb,hello\nworld", strict).is_ok());
+        assert!(sort_flight_protocol_with("1:\"a\"\ngarbage\n", strict).is_err());
+        assert!(sort_flight_protocol_with("1:{\"text\":\"open", strict).is_err());
+        assert!(sort_flight_protocol_with("1:Tff,short", strict).is_err());
+
+        assert!(sort_flight_protocol("1:\"a\"\ngarbage\n").ends_with("garbage\n"));
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod stream_tests {
+    use std::{
+        io::{Error, Result as IoResult},
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    use tokio::sync::mpsc;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_drain_chunked_stream_reassembles_suspense_document() {
+        let fallback = r#"<html><body><div id=root><!--$?--><template id="B:0"></template>Loading...<!--/$--></div>"#;
+        let resolved = r#"<div hidden id="S:0">Resolved</div><script>$RC("B:0","S:0")</script>"#;
+
+        let (sender, mut receiver) = mpsc::channel(8);
+        sender.send(Ok(fallback.as_bytes().to_vec())).await.unwrap();
+        sender.send(Ok(resolved.as_bytes().to_vec())).await.unwrap();
+        drop(sender);
+
+        let html = drain_chunked_stream(
+            Bytes::from_static(b"<!DOCTYPE html>"),
+            Bytes::from_static(b"</body></html>"),
+            &mut receiver,
+        )
+        .await
+        .unwrap();
+
+        assert!(html.starts_with("<!DOCTYPE html><html>"));
+        assert!(html.contains("Loading..."));
+        assert!(html.contains("Resolved"));
+        assert!(html.ends_with("</body></html>"));
+    }
+
+    #[tokio::test]
+    async fn test_drain_chunked_stream_propagates_chunk_errors() {
+        let (sender, mut receiver) = mpsc::channel(8);
+        sender.send(Ok(b"<div>partial</div>".to_vec())).await.unwrap();
+        sender.send(Err(RariError::internal("boundary failed"))).await.unwrap();
+        drop(sender);
+
+        let result = drain_chunked_stream(Bytes::new(), Bytes::new(), &mut receiver).await;
+
+        assert!(result.unwrap_err().to_string().contains("boundary failed"));
+    }
+
+    #[tokio::test]
+    async fn test_write_chunked_stream_writes_all_parts() {
+        let (sender, mut receiver) = mpsc::channel(8);
+        sender.send(Ok(b"<div>one</div>".to_vec())).await.unwrap();
+        sender.send(Ok(b"<div>two</div>".to_vec())).await.unwrap();
+        drop(sender);
+
+        let mut out = Vec::new();
+        let written = write_chunked_stream(
+            Bytes::from_static(b"<!DOCTYPE html>"),
+            Bytes::from_static(b"</html>"),
+            &mut receiver,
+            &mut out,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(out, b"<!DOCTYPE html><div>one</div><div>two</div></html>");
+        assert_eq!(written, out.len() as u64);
+    }
+
+    struct FailAfter {
+        remaining: usize,
+    }
+
+    impl AsyncWrite for FailAfter {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<IoResult<usize>> {
+            if self.remaining < buf.len() {
+                return Poll::Ready(Err(Error::other("disk full")));
+            }
+            self.remaining -= buf.len();
+            Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_chunked_stream_reports_write_failure_mid_stream() {
+        let (sender, mut receiver) = mpsc::channel(8);
+        sender.send(Ok(b"<div>fits</div>".to_vec())).await.unwrap();
+        sender.send(Ok(vec![b'x'; 64])).await.unwrap();
+        drop(sender);
+
+        let mut writer = FailAfter { remaining: 32 };
+        let err = write_chunked_stream(Bytes::new(), Bytes::new(), &mut receiver, &mut writer)
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("disk full"));
+    }
 }
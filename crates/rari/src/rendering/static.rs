@@ -1,6 +1,6 @@
 #![expect(clippy::missing_errors_doc)]
 
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock};
 
 use cow_utils::CowUtils;
 use rari_error::RariError;
@@ -8,7 +8,26 @@ use regex::Regex;
 use rustc_hash::FxHashSet;
 use tokio::fs;
 
-use crate::{runtime::JsExecutionRuntime, server::routing::app_router::AppRouteMatch};
+use crate::{
+    runtime::JsExecutionRuntime,
+    server::{
+        config::{Config, MissingRootPolicy, MissingTemplatePolicy, ScriptPlacement, ViteConfig},
+        routing::app_router::AppRouteMatch,
+    },
+};
+
+static CLASSIC_SRC_SCRIPT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    #[expect(clippy::expect_used, reason = "Infallible operation with valid inputs")]
+    Regex::new(r"<script\b([^>]*\bsrc=[^>]*)>").expect("classic script regex is valid")
+});
+
+/// Attributes that already keep a script from blocking the parser, matched as
+/// whole attribute names so `src="/async-loader.js"` or `data-defer` do not count.
+static NON_BLOCKING_SCRIPT_ATTR_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    #[expect(clippy::expect_used, reason = "Infallible operation with valid inputs")]
+    Regex::new(r#"(?i)(?:^|\s)(?:defer|async|type\s*=\s*["']?module["']?)(?:[\s=/]|$)"#)
+        .expect("non-blocking script attribute regex is valid")
+});
 
 pub fn escape_html(text: &str) -> String {
     text.cow_replace('&', "&amp;")
@@ -19,14 +38,115 @@ pub fn escape_html(text: &str) -> String {
         .into_owned()
 }
 
+/// A build-manifest asset to inject into rendered documents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AssetLink {
+    /// `<script type="module" src>`.
+    ModuleScript(String),
+    /// Classic `<script src>`.
+    Script(String),
+    /// `<link rel="modulepreload" href>`, placed in `<head>`.
+    ModulePreload(String),
+}
+
+impl AssetLink {
+    fn to_tag(&self) -> String {
+        match self {
+            Self::ModuleScript(src) => format!(
+                r#"<script type="module" src="{}"></script>"#,
+                RscHtmlRenderer::escape_html_attribute(src)
+            ),
+            Self::Script(src) => {
+                format!(
+                    r#"<script src="{}"></script>"#,
+                    RscHtmlRenderer::escape_html_attribute(src)
+                )
+            }
+            Self::ModulePreload(href) => format!(
+                r#"<link rel="modulepreload" href="{}">"#,
+                RscHtmlRenderer::escape_html_attribute(href)
+            ),
+        }
+    }
+
+    fn is_script(&self) -> bool {
+        matches!(self, Self::ModuleScript(_) | Self::Script(_))
+    }
+}
+
 pub struct RscHtmlRenderer {
     runtime: Arc<JsExecutionRuntime>,
     template_cache: parking_lot::Mutex<Option<String>>,
+    missing_root: MissingRootPolicy,
+    script_placement: ScriptPlacement,
+    missing_template: MissingTemplatePolicy,
+    asset_links: Option<Vec<AssetLink>>,
+    /// Dev entry module injected after `/@vite/client`; `None` disables injection.
+    vite_client_entry: Option<String>,
 }
 
 impl RscHtmlRenderer {
+    /// Starts from the default policies; callers pass configured ones in
+    /// through the `with_*` builders.
     pub fn new(runtime: Arc<JsExecutionRuntime>) -> Self {
-        Self { runtime, template_cache: parking_lot::Mutex::new(None) }
+        let vite = ViteConfig::default();
+        Self {
+            runtime,
+            template_cache: parking_lot::Mutex::new(None),
+            missing_root: MissingRootPolicy::default(),
+            script_placement: ScriptPlacement::default(),
+            missing_template: MissingTemplatePolicy::default(),
+            asset_links: None,
+            vite_client_entry: vite.inject_client.then_some(vite.client_entry),
+        }
+    }
+
+    /// What to do when the template has no root div; `new` starts at
+    /// [`MissingRootPolicy::Error`].
+    #[must_use]
+    pub fn with_missing_root_policy(mut self, policy: MissingRootPolicy) -> Self {
+        self.missing_root = policy;
+        self
+    }
+
+    #[must_use]
+    pub fn with_missing_template_policy(mut self, policy: MissingTemplatePolicy) -> Self {
+        self.missing_template = policy;
+        self
+    }
+
+    /// Inject these assets into complete documents instead of scraping script
+    /// and link tags out of the template.
+    #[must_use]
+    pub fn with_asset_links(mut self, asset_links: Vec<AssetLink>) -> Self {
+        self.asset_links = Some(asset_links);
+        self
+    }
+
+    /// Dev entry to pass to [`Self::with_vite_client_entry`] for `config`. Test
+    /// mode never injects the Vite client, whatever `vite.inject_client` says.
+    #[must_use]
+    pub fn vite_client_entry_for(config: &Config) -> Option<String> {
+        if config.is_test() {
+            return None;
+        }
+
+        config.vite.inject_client.then(|| config.vite.client_entry.clone())
+    }
+
+    /// Override the dev entry injected with the Vite client; `None` turns
+    /// injection off.
+    #[must_use]
+    pub fn with_vite_client_entry(mut self, entry: Option<String>) -> Self {
+        self.vite_client_entry = entry;
+        self
+    }
+
+    #[must_use]
+    pub fn with_script_placement(mut self, placement: ScriptPlacement) -> Self {
+        self.script_placement = placement;
+        self
     }
 
     fn extract_script_tags(template: &str) -> String {
@@ -110,14 +230,17 @@ impl RscHtmlRenderer {
         let template = match self.read_template_file(is_dev_mode).await {
             Ok(content) => {
                 if is_dev_mode {
-                    Self::inject_vite_client_if_needed(&content)
+                    self.inject_vite_client_if_needed(&content)
                 } else {
                     content
                 }
             }
             Err(e) => {
                 if is_dev_mode {
-                    Self::generate_dev_template_fallback()
+                    self.inject_vite_client_if_needed(&Self::generate_dev_template_fallback())
+                } else if self.missing_template == MissingTemplatePolicy::Fallback {
+                    tracing::warn!("{e}; serving the built-in fallback template");
+                    Self::generate_prod_template_fallback()
                 } else {
                     return Err(e);
                 }
@@ -132,19 +255,26 @@ impl RscHtmlRenderer {
         Ok(template)
     }
 
-    fn inject_vite_client_if_needed(html: &str) -> String {
+    fn inject_vite_client_if_needed(&self, html: &str) -> String {
+        let Some(entry) = self.vite_client_entry.as_deref() else {
+            return html.to_string();
+        };
+
         if html.contains("/@vite/client") || html.contains("@vite/client") {
             return html.to_string();
         }
 
+        let scripts = format!(
+            r#"<script type="module" src="/@vite/client"></script>
+<script type="module" src="{}"></script>
+"#,
+            Self::escape_html_attribute(entry)
+        );
+
         if let Some(head_end) = html.find("</head>") {
             let mut result = String::new();
             result.push_str(&html[..head_end]);
-            result.push_str(
-                r#"<script type="module" src="/@vite/client"></script>
-<script type="module" src="/src/main.tsx"></script>
-"#,
-            );
+            result.push_str(&scripts);
             result.push_str(&html[head_end..]);
             return result;
         }
@@ -152,20 +282,12 @@ impl RscHtmlRenderer {
         if let Some(body_end) = html.find("</body>") {
             let mut result = String::new();
             result.push_str(&html[..body_end]);
-            result.push_str(
-                r#"<script type="module" src="/@vite/client"></script>
-<script type="module" src="/src/main.tsx"></script>
-"#,
-            );
+            result.push_str(&scripts);
             result.push_str(&html[body_end..]);
             return result;
         }
 
-        format!(
-            r#"<script type="module" src="/@vite/client"></script>
-<script type="module" src="/src/main.tsx"></script>
-{html}"#
-        )
+        format!("{scripts}{html}")
     }
 
     fn generate_dev_template_fallback() -> String {
@@ -175,8 +297,6 @@ impl RscHtmlRenderer {
     <meta charset="UTF-8">
     <meta name="viewport" content="width=device-width, initial-scale=1.0">
     <title>rari App</title>
-    <script type="module" src="/@vite/client"></script>
-    <script type="module" src="/src/main.tsx"></script>
 </head>
 <body>
     <div id="root"></div>
@@ -185,14 +305,31 @@ impl RscHtmlRenderer {
             .to_string()
     }
 
-    async fn read_template_file(&self, is_dev_mode: bool) -> Result<String, RariError> {
-        let possible_paths = if is_dev_mode {
-            vec!["index.html", "public/index.html", "dist/index.html", "build/index.html"]
+    fn generate_prod_template_fallback() -> String {
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>rari App</title>
+</head>
+<body>
+    <div id="root"></div>
+</body>
+</html>"#
+            .to_string()
+    }
+
+    fn template_candidates(is_dev_mode: bool) -> [&'static str; 4] {
+        if is_dev_mode {
+            ["index.html", "public/index.html", "dist/index.html", "build/index.html"]
         } else {
-            vec!["dist/index.html", "build/index.html", "index.html", "public/index.html"]
-        };
+            ["dist/index.html", "build/index.html", "index.html", "public/index.html"]
+        }
+    }
 
-        for path in possible_paths {
+    async fn read_template_file(&self, is_dev_mode: bool) -> Result<String, RariError> {
+        for path in Self::template_candidates(is_dev_mode) {
             if let Ok(content) = fs::read_to_string(path).await {
                 return Ok(content);
             }
@@ -213,14 +350,23 @@ impl RscHtmlRenderer {
             Regex::new(r#"<div\s+id=["']root["'](?:\s+[^>]*)?\s*(?:/>|>\s*</div>)"#)
                 .map_err(|e| RariError::internal(format!("Failed to create regex: {e}")))?;
 
+        let replacement = format!(r#"<div id="root">{html_content}</div>"#);
+
         if !root_div_regex.is_match(template) {
-            return Err(RariError::internal(
-                "Template does not contain a root div with id='root'".to_string(),
-            ));
+            return match self.missing_root {
+                MissingRootPolicy::InjectIntoBody => {
+                    tracing::warn!("Template has no root div; injecting one into <body>");
+                    let mut result = template.to_string();
+                    let insert_at = result.rfind("</body>").unwrap_or(result.len());
+                    result.insert_str(insert_at, &replacement);
+                    Ok(result)
+                }
+                MissingRootPolicy::Error => Err(RariError::internal(
+                    "Template does not contain a root div with id='root'".to_string(),
+                )),
+            };
         }
 
-        let replacement = format!(r#"<div id="root">{html_content}</div>"#);
-
         // NoExpand: the rendered app HTML is a literal replacement, not a
         // pattern. Without it, `$0`/`$1`/`$&` in page content (e.g. a "$0.20"
         // headline) would be interpreted as capture-group references and expand
@@ -264,6 +410,21 @@ impl RscHtmlRenderer {
         css_links
     }
 
+    /// `Link` header values preloading the route's stylesheets.
+    pub(crate) fn preload_links(route_match: &AppRouteMatch, base_path: &str) -> Vec<String> {
+        let base = base_path.trim_matches('/');
+        Self::css_links_for_route(route_match)
+            .into_iter()
+            .map(|href| {
+                let href = match href.strip_prefix("/assets/") {
+                    Some(rest) if !base.is_empty() => format!("/{base}/assets/{rest}"),
+                    _ => href,
+                };
+                format!("<{href}>; rel=preload; as=style")
+            })
+            .collect()
+    }
+
     pub(crate) fn inject_css_links(template: &str, css_links: &[String]) -> String {
         if css_links.is_empty() {
             return template.to_string();
@@ -306,6 +467,13 @@ impl RscHtmlRenderer {
         if is_complete_document {
             let (script_tags, head_link_tags) = if is_dev_mode {
                 (String::new(), String::new())
+            } else if let Some(asset_links) = &self.asset_links {
+                let (scripts, links): (Vec<_>, Vec<_>) =
+                    asset_links.iter().partition(|link| link.is_script());
+                let join = |links: Vec<&AssetLink>| {
+                    links.into_iter().map(AssetLink::to_tag).collect::<Vec<_>>().join("\n")
+                };
+                (join(scripts), join(links))
             } else {
                 let template = self.load_template(cache_template, is_dev_mode).await?;
                 (
@@ -316,10 +484,8 @@ impl RscHtmlRenderer {
 
             let mut final_html = html_content;
 
-            if !script_tags.is_empty()
-                && let Some(body_end) = final_html.rfind("</body>")
-            {
-                final_html.insert_str(body_end, &format!("\n{script_tags}\n"));
+            if !script_tags.is_empty() {
+                final_html = self.place_script_tags(final_html, &script_tags);
             }
 
             final_html = Self::inject_css_links(&final_html, css_links);
@@ -338,6 +504,29 @@ impl RscHtmlRenderer {
         self.inject_into_template(&html_content, &template)
     }
 
+    fn place_script_tags(&self, mut html: String, script_tags: &str) -> String {
+        match self.script_placement {
+            ScriptPlacement::Head => {
+                let deferred =
+                    CLASSIC_SRC_SCRIPT_REGEX.replace_all(script_tags, |caps: &regex::Captures| {
+                        let attrs = &caps[1];
+                        if NON_BLOCKING_SCRIPT_ATTR_REGEX.is_match(attrs) {
+                            caps[0].to_string()
+                        } else {
+                            format!("<script defer{attrs}>")
+                        }
+                    });
+                Self::inject_head_tags(&html, &deferred)
+            }
+            ScriptPlacement::BodyEnd => {
+                if let Some(body_end) = html.rfind("</body>") {
+                    html.insert_str(body_end, &format!("\n{script_tags}\n"));
+                }
+                html
+            }
+        }
+    }
+
     fn escape_html_attribute(text: &str) -> String {
         text.cow_replace('&', "&amp;")
             .cow_replace('"', "&quot;")
@@ -353,8 +542,9 @@ mod tests {
     use rustc_hash::FxHashMap;
 
     use super::*;
-    use crate::server::routing::app_router::{
-        AppRouteEntry, AppRouteMatch, LayoutEntry, LoadingEntry,
+    use crate::server::{
+        config::Mode,
+        routing::app_router::{AppRouteEntry, AppRouteMatch, LayoutEntry, LoadingEntry},
     };
 
     fn sample_route_match() -> AppRouteMatch {
@@ -421,10 +611,60 @@ mod tests {
 
     #[test]
     fn test_generate_dev_template_fallback() {
-        let template = RscHtmlRenderer::generate_dev_template_fallback();
+        let runtime = Arc::new(JsExecutionRuntime::new(None));
+        let renderer = RscHtmlRenderer::new(runtime);
+        let template = renderer
+            .inject_vite_client_if_needed(&RscHtmlRenderer::generate_dev_template_fallback());
         assert!(template.contains("<!DOCTYPE html>"));
         assert!(template.contains(r#"<div id="root""#));
         assert!(template.contains("/@vite/client"));
+        assert!(template.contains(r#"src="/src/main.tsx""#));
+    }
+
+    #[test]
+    fn test_test_mode_reads_source_template_without_vite_client() {
+        let config = Config::new(Mode::Test);
+        assert!(!config.rsc_html.cache_template);
+        assert_eq!(
+            RscHtmlRenderer::template_candidates(config.uses_source_template()),
+            ["index.html", "public/index.html", "dist/index.html", "build/index.html"]
+        );
+
+        let runtime = Arc::new(JsExecutionRuntime::new(None));
+        let renderer = RscHtmlRenderer::new(runtime)
+            .with_vite_client_entry(RscHtmlRenderer::vite_client_entry_for(&config));
+        let html = renderer
+            .inject_vite_client_if_needed(&RscHtmlRenderer::generate_dev_template_fallback());
+        assert!(!html.contains("/@vite/client"));
+        assert!(html.contains(r#"<div id="root""#));
+    }
+
+    #[test]
+    fn test_inject_vite_client_uses_configured_entry() {
+        let runtime = Arc::new(JsExecutionRuntime::new(None));
+        let renderer = RscHtmlRenderer::new(Arc::clone(&runtime))
+            .with_vite_client_entry(Some("/src/index.tsx".to_string()));
+        let html = renderer.inject_vite_client_if_needed("<html><head></head><body></body></html>");
+        assert!(html.contains(r#"<script type="module" src="/src/index.tsx"></script>"#));
+        assert!(!html.contains("/src/main.tsx"));
+
+        let disabled = RscHtmlRenderer::new(runtime).with_vite_client_entry(None);
+        let html = disabled.inject_vite_client_if_needed("<html><head></head></html>");
+        assert!(!html.contains("@vite/client"));
+    }
+
+    #[tokio::test]
+    async fn test_load_template_missing_in_production() {
+        let runtime = Arc::new(JsExecutionRuntime::new(None));
+        let renderer = RscHtmlRenderer::new(Arc::clone(&runtime))
+            .with_missing_template_policy(MissingTemplatePolicy::Error);
+        assert!(renderer.load_template(false, false).await.is_err());
+
+        let renderer = RscHtmlRenderer::new(runtime)
+            .with_missing_template_policy(MissingTemplatePolicy::Fallback);
+        let template = renderer.load_template(false, false).await.unwrap();
+        assert!(template.contains(r#"<div id="root"></div>"#));
+        assert!(!template.contains("/@vite/client"));
     }
 
     #[test]
@@ -484,6 +724,80 @@ mod tests {
         assert!(html.contains(r#"<div id="root"><p>Hi</p></div>"#));
     }
 
+    #[test]
+    fn test_inject_into_template_missing_root_errors_with_error_policy() {
+        let runtime = Arc::new(JsExecutionRuntime::new(None));
+        let renderer =
+            RscHtmlRenderer::new(runtime).with_missing_root_policy(MissingRootPolicy::Error);
+
+        let template = r#"<html><body><div id="app"></div></body></html>"#;
+        assert!(renderer.inject_into_template("<p>Hi</p>", template).is_err());
+    }
+
+    #[test]
+    fn test_inject_into_template_missing_root_injects_into_body() {
+        let runtime = Arc::new(JsExecutionRuntime::new(None));
+        let renderer = RscHtmlRenderer::new(runtime)
+            .with_missing_root_policy(MissingRootPolicy::InjectIntoBody);
+
+        let template = r#"<html><body><header>Nav</header></body></html>"#;
+        let html = renderer.inject_into_template("<p>Hi</p>", template).unwrap();
+        assert_eq!(
+            html,
+            r#"<html><body><header>Nav</header><div id="root"><p>Hi</p></div></body></html>"#
+        );
+
+        let html = renderer.inject_into_template("<p>Hi</p>", "<main></main>").unwrap();
+        assert_eq!(html, r#"<main></main><div id="root"><p>Hi</p></div>"#);
+    }
+
+    #[test]
+    fn test_place_script_tags_body_end_follows_content() {
+        let runtime = Arc::new(JsExecutionRuntime::new(None));
+        let renderer =
+            RscHtmlRenderer::new(runtime).with_script_placement(ScriptPlacement::BodyEnd);
+        let html = "<html><head></head><body><main>Page</main></body></html>".to_string();
+
+        let html = renderer.place_script_tags(html, r#"<script src="/assets/app.js"></script>"#);
+
+        let main = html.find("<main>").unwrap();
+        let script = html.find("<script").unwrap();
+        assert!(script > main);
+        assert!(html.contains(r#"<script src="/assets/app.js"></script>"#));
+    }
+
+    #[test]
+    fn test_place_script_tags_head_defers_classic_scripts() {
+        let runtime = Arc::new(JsExecutionRuntime::new(None));
+        let renderer = RscHtmlRenderer::new(runtime).with_script_placement(ScriptPlacement::Head);
+        let html = "<html><head></head><body><main>Page</main></body></html>".to_string();
+        let scripts = "<script src=\"/assets/legacy.js\"></script>\n<script type=\"module\" src=\"/assets/app.js\"></script>";
+
+        let html = renderer.place_script_tags(html, scripts);
+
+        let head_end = html.find("</head>").unwrap();
+        assert!(html.find("/assets/legacy.js").unwrap() < head_end);
+        assert!(html.contains(r#"<script defer src="/assets/legacy.js"></script>"#));
+        assert!(html.contains(r#"<script type="module" src="/assets/app.js"></script>"#));
+    }
+
+    #[test]
+    fn test_place_script_tags_head_matches_whole_attribute_names() {
+        let runtime = Arc::new(JsExecutionRuntime::new(None));
+        let renderer = RscHtmlRenderer::new(runtime).with_script_placement(ScriptPlacement::Head);
+        let html = "<html><head></head><body></body></html>".to_string();
+        let scripts = "<script src=\"/assets/async-loader.js\" data-defer=\"1\"></script>\n<script async src=\"/assets/analytics.js\"></script>";
+
+        let html = renderer.place_script_tags(html, scripts);
+
+        assert!(
+            html.contains(
+                r#"<script defer src="/assets/async-loader.js" data-defer="1"></script>"#
+            )
+        );
+        assert!(html.contains(r#"<script async src="/assets/analytics.js"></script>"#));
+    }
+
     #[test]
     fn test_extract_non_stylesheet_link_tags() {
         let template = r#"<html><head>
@@ -537,6 +851,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_preload_links_for_route_chunks() {
+        let mut route_match = sample_route_match();
+        route_match.layouts.clear();
+        route_match.loading = None;
+        route_match.route.css =
+            vec!["/assets/layout-a1.css".to_string(), "/assets/page-b2.css".to_string()];
+
+        assert_eq!(
+            RscHtmlRenderer::preload_links(&route_match, "/"),
+            vec![
+                "</assets/layout-a1.css>; rel=preload; as=style".to_string(),
+                "</assets/page-b2.css>; rel=preload; as=style".to_string(),
+            ]
+        );
+        assert_eq!(
+            RscHtmlRenderer::preload_links(&route_match, "/app/")[0],
+            "</app/assets/layout-a1.css>; rel=preload; as=style"
+        );
+    }
+
     #[tokio::test]
     async fn test_assemble_document_wraps_fragment_in_dev_template() {
         let runtime = Arc::new(JsExecutionRuntime::new(None));
@@ -552,6 +887,36 @@ mod tests {
         assert!(html.contains("/@vite/client"));
     }
 
+    #[tokio::test]
+    async fn test_assemble_document_uses_supplied_asset_links() {
+        let runtime = Arc::new(JsExecutionRuntime::new(None));
+        let renderer = RscHtmlRenderer::new(runtime)
+            .with_missing_template_policy(MissingTemplatePolicy::Error)
+            .with_script_placement(ScriptPlacement::BodyEnd)
+            .with_asset_links(vec![
+                AssetLink::ModuleScript("/assets/main-abc123.js".to_string()),
+                AssetLink::ModulePreload("/assets/vendor-def456.js".to_string()),
+            ]);
+        let html_content =
+            "<!DOCTYPE html><html><head></head><body><main>Page</main></body></html>";
+
+        // No template exists for a production load, so success means it was not read.
+        let html = renderer
+            .assemble_document(html_content.to_string(), false, false, &[])
+            .await
+            .expect("assemble_document should not need the template");
+
+        assert!(html.contains(
+            r#"<script type="module" src="/assets/main-abc123.js"></script>
+</body>"#
+        ));
+        let head_end = html.find("</head>").expect("head");
+        let preload = html
+            .find(r#"<link rel="modulepreload" href="/assets/vendor-def456.js">"#)
+            .expect("modulepreload link");
+        assert!(preload < head_end);
+    }
+
     #[tokio::test]
     async fn test_assemble_document_complete_doc_injects_css() {
         let runtime = Arc::new(JsExecutionRuntime::new(None));
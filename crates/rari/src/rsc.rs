@@ -12,6 +12,8 @@ use rari_error::RariError;
 use regex::Regex;
 use rustc_hash::{FxHashMap, FxHashSet};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
 use smallvec::SmallVec;
 
 pub type DependencyList = SmallVec<[String; 4]>;
@@ -54,6 +56,48 @@ pub fn hash_string(s: &str) -> String {
     format!("{:x}", hasher.finish())
 }
 
+/// Key for caching a rendered server component. Props are hashed with object keys
+/// sorted so `{a, b}` and `{b, a}` share an entry; the component id stays readable
+/// as a prefix so per-component invalidation can match on it.
+pub fn cache_key(component_id: &str, props: &Value) -> String {
+    let mut canonical = String::new();
+    write_canonical_json(props, &mut canonical);
+
+    let digest = Sha256::digest(canonical.as_bytes());
+    format!("{component_id}:{}", hex::encode(digest))
+}
+
+fn write_canonical_json(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+
+            out.push('{');
+            for (index, (key, entry)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical_json(entry, out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_canonical_json(item, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
 static CIRCULAR_DETECTION: OnceLock<Mutex<FxHashSet<String>>> = OnceLock::new();
 
 type ComponentDependencies = DependencyList;
@@ -101,6 +145,46 @@ pub struct TransformedComponent {
     pub reload_attempt_count: usize,
 }
 
+/// A client component importing a server component directly. Server components
+/// may render client components, but a client module cannot pull a server
+/// component into the browser bundle; it has to receive it as `children` or
+/// another prop instead.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("client component `{component}` imports server component `{dependency}` via `{import}`")]
+#[non_exhaustive]
+pub struct BoundaryViolation {
+    pub component: String,
+    pub dependency: String,
+    pub import: String,
+}
+
+impl TransformedComponent {
+    /// Check this component's imports against the registry. Only client
+    /// components can violate the boundary: any registered dependency that is
+    /// not itself a client reference is treated as a server component.
+    #[expect(clippy::missing_errors_doc)]
+    pub fn validate_boundary(&self, registry: &ComponentRegistry) -> Result<(), BoundaryViolation> {
+        if !self.is_client_reference {
+            return Ok(());
+        }
+
+        for import in &self.dependencies {
+            if let Some(dependency) = registry.resolve_dependency(import)
+                && dependency.id != self.id
+                && !dependency.is_client_reference
+            {
+                return Err(BoundaryViolation {
+                    component: self.id.clone(),
+                    dependency: dependency.id.clone(),
+                    import: import.clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
 pub struct ComponentRegistry {
     components: FxHashMap<String, TransformedComponent>,
     dependency_graph: FxHashMap<String, FxHashSet<String>>,
@@ -450,26 +534,28 @@ impl ComponentRegistry {
     }
 
     pub fn find_dependency_code(&self, id: &str) -> Option<String> {
+        self.resolve_dependency(id).map(|component| component.source.clone())
+    }
+
+    /// Registered component an import specifier refers to, trying the exact id,
+    /// then the id without relative prefix and extension, then a path suffix.
+    pub fn resolve_dependency(&self, id: &str) -> Option<&TransformedComponent> {
         let normalized_id = Self::normalize_id(id);
 
         if let Some(component) = self.components.get(normalized_id.as_ref()) {
-            return Some(component.source.clone());
+            return Some(component);
         }
 
         let further_normalized_id = Self::normalize_component_id(normalized_id.as_ref());
         if let Some(component) = self.components.get(&further_normalized_id) {
-            return Some(component.source.clone());
+            return Some(component);
         }
 
-        for (component_id, component) in &self.components {
-            if component_id.ends_with(&format!("/{further_normalized_id}"))
-                || component_id == &further_normalized_id
-            {
-                return Some(component.source.clone());
-            }
-        }
-
-        None
+        self.components.iter().find_map(|(component_id, component)| {
+            (component_id.ends_with(&format!("/{further_normalized_id}"))
+                || component_id == &further_normalized_id)
+                .then_some(component)
+        })
     }
 
     pub fn remove_component(&mut self, id: &str) {
@@ -536,6 +622,28 @@ mod tests {
         assert!(dependencies.contains(&"../components/Card".to_string()));
     }
 
+    #[test]
+    fn test_cache_key_ignores_prop_order() {
+        let a = serde_json::json!({ "id": 1, "filter": { "tag": "rust", "limit": 10 } });
+        let b = serde_json::json!({ "filter": { "limit": 10, "tag": "rust" }, "id": 1 });
+
+        assert_eq!(cache_key("Widget", &a), cache_key("Widget", &b));
+        assert!(cache_key("Widget", &a).starts_with("Widget:"));
+    }
+
+    #[test]
+    fn test_cache_key_differs_by_props_and_component() {
+        let one = serde_json::json!({ "id": 1 });
+        let two = serde_json::json!({ "id": 2 });
+
+        assert_ne!(cache_key("Widget", &one), cache_key("Widget", &two));
+        assert_ne!(cache_key("Widget", &one), cache_key("Gadget", &one));
+        assert_ne!(
+            cache_key("Widget", &serde_json::json!({ "id": "1" })),
+            cache_key("Widget", &one)
+        );
+    }
+
     #[test]
     fn test_component_type() {
         let client_type = ComponentType::Client;
@@ -802,6 +910,52 @@ mod tests {
         assert_eq!(registry.get_module_specifier("components/Button"), Some("file:///button.js"));
     }
 
+    #[test]
+    fn test_validate_boundary_flags_client_importing_server() {
+        let mut registry = ComponentRegistry::new();
+        registry
+            .register_component(
+                "components/ServerList",
+                "export default async function ServerList() { return <ul />; }",
+                "transformed".to_string(),
+                SmallVec::new(),
+            )
+            .unwrap();
+        registry
+            .register_component(
+                "components/Button",
+                "'use client'; export default function Button() { return <button />; }",
+                "transformed".to_string(),
+                SmallVec::new(),
+            )
+            .unwrap();
+        registry
+            .register_component(
+                "components/Toolbar",
+                "'use client'; import Button from './Button'; import ServerList from './ServerList';",
+                "transformed".to_string(),
+                smallvec!["./Button".to_string(), "./ServerList".to_string()],
+            )
+            .unwrap();
+        registry.register_client_reference("components/Button", "components/Button.tsx", "default");
+        registry.register_client_reference(
+            "components/Toolbar",
+            "components/Toolbar.tsx",
+            "default",
+        );
+
+        let toolbar = registry.get_component("components/Toolbar").unwrap();
+        let violation = toolbar.validate_boundary(&registry).unwrap_err();
+        assert_eq!(violation.component, "components/Toolbar");
+        assert_eq!(violation.dependency, "components/ServerList");
+        assert_eq!(violation.import, "./ServerList");
+
+        let button = registry.get_component("components/Button").unwrap();
+        assert!(button.validate_boundary(&registry).is_ok());
+        let server = registry.get_component("components/ServerList").unwrap();
+        assert!(server.validate_boundary(&registry).is_ok());
+    }
+
     #[test]
     fn test_client_reference_normalization() {
         let mut registry = ComponentRegistry::new();
@@ -3,6 +3,7 @@
 use std::{
     env,
     fmt::Write,
+    net::IpAddr,
     str,
     sync::{
         Arc,
@@ -13,7 +14,7 @@ use std::{
 use axum::{
     body::{Body, Bytes},
     extract::State,
-    http::{HeaderMap, StatusCode, Uri, header},
+    http::{Extensions, HeaderMap, StatusCode, Uri, header},
     response::Response,
 };
 use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64_STANDARD};
@@ -35,8 +36,11 @@ use crate::{
     server::{
         ServerState,
         cache::revalidate::{invalidate_route_caches, invalidate_route_caches_on},
-        config::RedirectConfig,
-        core::utils::http::{extract_headers, extract_search_params, is_origin_allowed},
+        config::{RedirectConfig, ServerConfig},
+        core::utils::http::{
+            extract_headers, extract_search_params, is_origin_allowed, peer_ip,
+            resolve_request_origin,
+        },
         error_response,
         middleware::request_context::{PendingCookie, PendingCookieKey, RequestContext},
     },
@@ -63,25 +67,21 @@ fn normalize_origin(url: &url::Url) -> (String, String, u16) {
     (url.scheme().to_string(), url.host_str().unwrap_or("").to_string(), effective_port(url))
 }
 
-fn check_origin(headers: &HeaderMap, allowed_origins: &[String]) -> Result<(), RariError> {
+fn check_origin(
+    headers: &HeaderMap,
+    allowed_origins: &[String],
+    peer: Option<IpAddr>,
+    server: &ServerConfig,
+) -> Result<(), RariError> {
     if allowed_origins.is_empty() {
-        let host = headers.get("host").and_then(|v| v.to_str().ok()).ok_or_else(|| {
-            tracing::error!("Missing host header in server action request");
-            RariError::bad_request("Missing host header")
-        })?;
-
-        let scheme = headers
-            .get("x-forwarded-proto")
-            .or_else(|| headers.get("x-forwarded-protocol"))
-            .and_then(|v| v.to_str().ok())
-            .unwrap_or_else(|| {
-                tracing::debug!(
-                    "No x-forwarded-proto header; defaulting to http for origin validation"
-                );
-                "http"
-            });
-
-        let server_origin_str = format!("{scheme}://{host}");
+        // Forwarded scheme/host only count when the peer is a trusted proxy.
+        let server_origin_str =
+            resolve_request_origin(&extract_headers(headers), peer, server, "http")
+                .ok_or_else(|| {
+                    tracing::error!("Missing host header in server action request");
+                    RariError::bad_request("Missing host header")
+                })?
+                .to_origin_string();
         let server_origin_url = url::Url::parse(&server_origin_str).map_err(|e| {
             tracing::error!("Failed to parse server origin: {}", e);
             RariError::internal(format!("Failed to parse server origin: {e}"))
@@ -625,32 +625,43 @@ async fn compose_action_refresh_route(
 pub async fn handle_server_action(
     State(state): State<ServerState>,
     headers: HeaderMap,
+    extensions: Extensions,
     body: Bytes,
 ) -> Result<Response, StatusCode> {
-    handle_server_action_at_path(state, "/_rari/action".to_string(), headers, body).await
+    handle_server_action_at_path(
+        state,
+        "/_rari/action".to_string(),
+        headers,
+        peer_ip(&extensions),
+        body,
+    )
+    .await
 }
 
 pub async fn handle_page_server_action(
     State(state): State<ServerState>,
     uri: Uri,
     headers: HeaderMap,
+    extensions: Extensions,
     body: Bytes,
 ) -> Result<Response, StatusCode> {
     if !is_server_action_request(&headers) {
         return Err(StatusCode::METHOD_NOT_ALLOWED);
     }
 
-    handle_server_action_at_path(state, uri.path().to_string(), headers, body).await
+    handle_server_action_at_path(state, uri.path().to_string(), headers, peer_ip(&extensions), body)
+        .await
 }
 
 async fn handle_server_action_at_path(
     state: ServerState,
     request_path: String,
     headers: HeaderMap,
+    peer: Option<IpAddr>,
     body: Bytes,
 ) -> Result<Response, StatusCode> {
     let allowed_origins = state.config.action_origins();
-    if let Err(e) = check_origin(&headers, &allowed_origins) {
+    if let Err(e) = check_origin(&headers, &allowed_origins, peer, &state.config.server) {
         return Ok(rpc_action_error_response(&e, state.config.is_development(), None));
     }
 
@@ -1096,6 +1107,13 @@ mod tests {
     use super::*;
     use crate::server::{config::RedirectConfig, middleware::request_context::PendingCookie};
 
+    /// `check_origin` for a request arriving through the trusted proxy `10.0.0.1`.
+    fn check_origin_via_proxy(headers: &HeaderMap) -> Result<(), RariError> {
+        let mut server = ServerConfig::default();
+        server.trusted_proxies = vec!["10.0.0.1".parse().unwrap()];
+        check_origin(headers, &[], Some("10.0.0.1".parse().unwrap()), &server)
+    }
+
     #[test]
     fn test_redirect_relative_url_allowed() {
         let config =
@@ -1515,14 +1533,12 @@ mod tests {
     fn test_origin_comparison_with_default_https_port() {
         use axum::http::HeaderMap;
 
-        use super::check_origin;
-
         let mut headers = HeaderMap::new();
         headers.insert("host", "example.com".parse().unwrap());
         headers.insert("x-forwarded-proto", "https".parse().unwrap());
         headers.insert("origin", "https://example.com:443".parse().unwrap());
 
-        let result = check_origin(&headers, &[]);
+        let result = check_origin_via_proxy(&headers);
         assert!(
             result.is_ok(),
             "Origin with explicit default HTTPS port (443) should match server origin without port"
@@ -1533,14 +1549,12 @@ mod tests {
     fn test_origin_comparison_with_default_http_port() {
         use axum::http::HeaderMap;
 
-        use super::check_origin;
-
         let mut headers = HeaderMap::new();
         headers.insert("host", "example.com".parse().unwrap());
         headers.insert("x-forwarded-proto", "http".parse().unwrap());
         headers.insert("origin", "http://example.com:80".parse().unwrap());
 
-        let result = check_origin(&headers, &[]);
+        let result = check_origin_via_proxy(&headers);
         assert!(
             result.is_ok(),
             "Origin with explicit default HTTP port (80) should match server origin without port"
@@ -1551,14 +1565,12 @@ mod tests {
     fn test_origin_comparison_with_explicit_port_in_host() {
         use axum::http::HeaderMap;
 
-        use super::check_origin;
-
         let mut headers = HeaderMap::new();
         headers.insert("host", "example.com:8080".parse().unwrap());
         headers.insert("x-forwarded-proto", "https".parse().unwrap());
         headers.insert("origin", "https://example.com:8080".parse().unwrap());
 
-        let result = check_origin(&headers, &[]);
+        let result = check_origin_via_proxy(&headers);
         assert!(
             result.is_ok(),
             "Origin with explicit non-default port should match server origin with same port"
@@ -1569,14 +1581,12 @@ mod tests {
     fn test_origin_comparison_port_mismatch() {
         use axum::http::HeaderMap;
 
-        use super::check_origin;
-
         let mut headers = HeaderMap::new();
         headers.insert("host", "example.com:8080".parse().unwrap());
         headers.insert("x-forwarded-proto", "https".parse().unwrap());
         headers.insert("origin", "https://example.com:9090".parse().unwrap());
 
-        let result = check_origin(&headers, &[]);
+        let result = check_origin_via_proxy(&headers);
         assert!(result.is_err(), "Origin with different port should not match");
     }
 
@@ -1584,20 +1594,74 @@ mod tests {
     fn test_referer_comparison_with_default_port() {
         use axum::http::HeaderMap;
 
-        use super::check_origin;
-
         let mut headers = HeaderMap::new();
         headers.insert("host", "example.com".parse().unwrap());
         headers.insert("x-forwarded-proto", "https".parse().unwrap());
         headers.insert("referer", "https://example.com:443/some/path".parse().unwrap());
 
-        let result = check_origin(&headers, &[]);
+        let result = check_origin_via_proxy(&headers);
         assert!(
             result.is_ok(),
             "Referer with explicit default HTTPS port (443) should match server origin without port"
         );
     }
 
+    #[test]
+    fn test_origin_cross_origin_rejected_without_allowed_origins() {
+        use axum::http::HeaderMap;
+
+        let mut headers = HeaderMap::new();
+        headers.insert("host", "example.com".parse().unwrap());
+        headers.insert("x-forwarded-proto", "https".parse().unwrap());
+        headers.insert("origin", "https://evil.example".parse().unwrap());
+
+        let err = check_origin_via_proxy(&headers).expect_err("cross-origin must be rejected");
+        assert_eq!(err.status_code(), 403);
+    }
+
+    #[test]
+    fn test_origin_checked_against_configured_allowed_origins() {
+        use axum::http::HeaderMap;
+
+        use super::check_origin;
+
+        let allowed = vec!["https://app.example.com".to_string()];
+
+        let mut same = HeaderMap::new();
+        same.insert("origin", "https://app.example.com".parse().unwrap());
+        assert!(check_origin(&same, &allowed, None, &ServerConfig::default()).is_ok());
+
+        let mut cross = HeaderMap::new();
+        cross.insert("origin", "https://evil.example".parse().unwrap());
+        let err = check_origin(&cross, &allowed, None, &ServerConfig::default())
+            .expect_err("cross-origin must be rejected");
+        assert_eq!(err.status_code(), 403);
+    }
+
+    #[test]
+    fn test_origin_ignores_forwarded_proto_from_untrusted_peer() {
+        use axum::http::HeaderMap;
+
+        use super::check_origin;
+
+        let mut server = ServerConfig::default();
+        server.trusted_proxies = vec!["10.0.0.1".parse().unwrap()];
+
+        let mut headers = HeaderMap::new();
+        headers.insert("host", "example.com".parse().unwrap());
+        headers.insert("x-forwarded-proto", "https".parse().unwrap());
+        headers.insert("origin", "https://example.com".parse().unwrap());
+
+        assert!(check_origin(&headers, &[], Some("10.0.0.1".parse().unwrap()), &server).is_ok());
+
+        let err = check_origin(&headers, &[], Some("203.0.113.7".parse().unwrap()), &server)
+            .expect_err("forwarded proto from an untrusted peer must not widen the origin");
+        assert_eq!(err.status_code(), 403);
+
+        server.trust_forwarded_headers = false;
+        assert!(check_origin(&headers, &[], Some("10.0.0.1".parse().unwrap()), &server).is_err());
+    }
+
     #[test]
     fn test_is_failed_action_result() {
         assert!(is_failed_action_result(&serde_json::json!({ "success": false })));
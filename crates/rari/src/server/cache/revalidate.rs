@@ -1,6 +1,10 @@
 #![expect(clippy::missing_errors_doc)]
 
-use std::{env, sync::Arc};
+use std::{
+    env,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use axum::{extract::State, http::StatusCode, response::Json};
 use rari_error::RariError;
@@ -30,6 +34,51 @@ fn constant_time_eq(a: &str, b: &str) -> bool {
     diff == 0
 }
 
+const REVALIDATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Fixed one-minute window shared by all authenticated revalidate requests to
+/// one server. A `limit` of `0` disables the cap.
+#[derive(Debug)]
+pub struct RevalidateRateLimiter {
+    limit: u32,
+    window: parking_lot::Mutex<Option<(Instant, u32)>>,
+}
+
+impl RevalidateRateLimiter {
+    #[must_use]
+    pub fn new(limit: u32) -> Self {
+        Self { limit, window: parking_lot::Mutex::new(None) }
+    }
+
+    fn try_acquire(&self, now: Instant) -> bool {
+        let limit = self.limit;
+        if limit == 0 {
+            return true;
+        }
+
+        let mut window = self.window.lock();
+        match window.as_mut() {
+            Some((start, count)) if now.duration_since(*start) < REVALIDATE_WINDOW => {
+                if *count >= limit {
+                    return false;
+                }
+                *count += 1;
+            }
+            _ => *window = Some((now, 1)),
+        }
+        true
+    }
+}
+
+fn check_revalidate_rate_limit(state: &ServerState) -> Result<(), StatusCode> {
+    if state.revalidate_limiter.try_acquire(Instant::now()) {
+        Ok(())
+    } else {
+        tracing::warn!(limit = state.revalidate_limiter.limit, "Revalidation rate limit exceeded");
+        Err(StatusCode::TOO_MANY_REQUESTS)
+    }
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(tag = "type", rename_all = "lowercase")]
 #[non_exhaustive]
@@ -180,6 +229,8 @@ pub async fn revalidate_by_path(
                 }
             }
 
+            check_revalidate_rate_limit(&state)?;
+
             let res = match invalidate_route_caches(&state, path).await {
                 Ok(()) => RevalidateResponse {
                     revalidated: true,
@@ -209,6 +260,8 @@ pub async fn revalidate_by_path(
                 }
             }
 
+            check_revalidate_rate_limit(&state)?;
+
             state.response_cache.invalidate_by_tag(tag).await;
             response::invalidate_static_fast_cache_for_path(&state.static_fast_cache, tag);
             let use_cache_result =
@@ -245,3 +298,39 @@ pub async fn revalidate_by_path(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_revalidate_rate_limiter_caps_requests_per_window() {
+        let limiter = RevalidateRateLimiter::new(2);
+        let start = Instant::now();
+
+        assert!(limiter.try_acquire(start));
+        assert!(limiter.try_acquire(start + Duration::from_secs(1)));
+        assert!(!limiter.try_acquire(start + Duration::from_secs(59)));
+
+        assert!(limiter.try_acquire(start + REVALIDATE_WINDOW));
+    }
+
+    #[test]
+    fn test_revalidate_rate_limiter_zero_is_unlimited() {
+        let limiter = RevalidateRateLimiter::new(0);
+        let now = Instant::now();
+
+        assert!((0..1000).all(|_| limiter.try_acquire(now)));
+    }
+
+    #[test]
+    fn test_revalidate_rate_limiters_do_not_share_a_window() {
+        let first = RevalidateRateLimiter::new(1);
+        let second = RevalidateRateLimiter::new(1);
+        let now = Instant::now();
+
+        assert!(first.try_acquire(now));
+        assert!(!first.try_acquire(now));
+        assert!(second.try_acquire(now));
+    }
+}
@@ -277,5 +277,6 @@ fn create_warmup_context(route_match: &AppRouteMatch) -> LayoutRenderContext {
         template_navigation_id: None,
         metadata: None,
         streaming_head_extra: None,
+        client_ip: None,
     }
 }
@@ -307,6 +307,33 @@ mod tests {
         assert!(chunks.iter().all(|c| !c.is_empty()));
     }
 
+    #[tokio::test]
+    async fn test_gzip_streamed_html_round_trips_with_per_chunk_flush() {
+        use async_compression::tokio::bufread::GzipDecoder;
+        use tokio::io::AsyncReadExt;
+
+        let parts = [
+            "<!DOCTYPE html><html><body><div id=\"root\">",
+            "<template id=\"B:0\"></template>Loading...",
+            "<div hidden id=\"S:0\"><p>Resolved</p></div>",
+            "</div></body></html>",
+        ];
+        let input_stream = stream::iter(parts.iter().map(|p| Ok(Bytes::from(*p))));
+        let mut compressed_stream = compress_stream(input_stream, CompressionEncoding::Gzip);
+
+        let mut chunks = Vec::new();
+        while let Some(chunk) = compressed_stream.next().await {
+            chunks.push(chunk.unwrap());
+        }
+
+        assert_eq!(chunks.len(), parts.len() + 1, "one flushed frame per input chunk + trailer");
+
+        let compressed: Vec<u8> = chunks.concat();
+        let mut decoded = String::new();
+        GzipDecoder::new(compressed.as_slice()).read_to_string(&mut decoded).await.unwrap();
+        assert_eq!(decoded, parts.concat());
+    }
+
     #[tokio::test]
     async fn test_identity_passthrough() {
         let input_stream = stream::iter(vec![Ok(Bytes::from("Hello ")), Ok(Bytes::from("World"))]);
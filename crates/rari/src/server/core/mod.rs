@@ -7,7 +7,7 @@ use std::{
     net::SocketAddr,
     path::PathBuf,
     sync::{Arc, atomic::AtomicU64},
-    time::Instant,
+    time::{Duration, Instant},
 };
 
 use axum::{
@@ -39,8 +39,11 @@ use crate::{
     server::{
         actions::{handle_page_server_action, handle_server_action},
         cache::{
-            handler::CacheHandlerRegistry, loader::CacheLoader, response,
-            revalidate::revalidate_by_path, warmup,
+            handler::CacheHandlerRegistry,
+            loader::CacheLoader,
+            response,
+            revalidate::{RevalidateRateLimiter, revalidate_by_path},
+            warmup,
         },
         config::{
             CACHE_LAYER_IMAGE, CACHE_LAYER_LAYOUT, CACHE_LAYER_OG, CACHE_LAYER_RESPONSE, Config,
@@ -51,7 +54,11 @@ use crate::{
             proxy::{self, ProxyLayer},
             request::{cors_middleware, security_headers_middleware},
         },
-        og::{OgImageCache, OgImageGenerator, og_image_handler, og_image_handler_root},
+        og::{
+            OgImageCache, OgImageGenerator, OgRenderLimiter, og_image_handler,
+            og_image_handler_root,
+        },
+        rendering::{hmr_cancel::HmrRenderCancellation, render_queue::RenderScheduler},
         routing::{
             RoutesManifest,
             api::{api_cors_preflight, handle_api_route},
@@ -66,7 +73,7 @@ use crate::{
         vite::{
             check_vite_server_health,
             hmr::handle_hmr_action,
-            rsc::{health_check, register_client_component, register_component},
+            rsc::{config_dump, health_check, register_client_component, register_component},
             vite_reverse_proxy, vite_src_proxy, vite_websocket_proxy,
         },
     },
@@ -167,7 +174,11 @@ impl Server {
 
         let ssr_renderer = {
             let runtime = Arc::clone(&renderer.runtime);
-            let ssr = RscHtmlRenderer::new(runtime);
+            let ssr = RscHtmlRenderer::new(runtime)
+                .with_missing_root_policy(config.rsc_html.missing_root)
+                .with_missing_template_policy(config.rsc_html.missing_template)
+                .with_script_placement(config.rsc_html.script_placement)
+                .with_vite_client_entry(RscHtmlRenderer::vite_client_entry_for(&config));
             Arc::new(ssr)
         };
 
@@ -207,11 +218,14 @@ impl Server {
         let og_generator = {
             let runtime = Arc::clone(&js_runtime);
             let og_cache = OgImageCache::with_handler(og_handler, &project_root);
-            let generator = Arc::new(OgImageGenerator::with_capacity_and_cache(
-                runtime,
-                project_root.clone(),
-                og_cache,
-            ));
+            let render_limiter = OgRenderLimiter::new(
+                config.images.og_render_concurrency,
+                Duration::from_millis(config.images.og_render_queue_timeout_ms),
+            );
+            let generator = Arc::new(
+                OgImageGenerator::with_capacity_and_cache(runtime, project_root.clone(), og_cache)
+                    .with_render_limiter(render_limiter),
+            );
 
             if let Ok(manifest) = &routes_manifest {
                 if let Err(e) =
@@ -248,6 +262,13 @@ impl Server {
             image_optimizer: None,
             cache_registry: Arc::clone(&cache_registry),
             image_handler,
+            render_scheduler: Arc::new(RenderScheduler::new(config.render_queue.max_concurrent)),
+            hmr_render_cancellation: Arc::new(HmrRenderCancellation::new(
+                config.is_development() && config.vite.enable_hmr_proxy,
+            )),
+            revalidate_limiter: Arc::new(RevalidateRateLimiter::new(
+                config.server.revalidate_requests_per_minute,
+            )),
         };
 
         if config.is_production() {
@@ -342,6 +363,7 @@ impl Server {
                 .route("/_rari/hmr", routing::post(handle_hmr_action))
                 .route("/_rari/hmr", routing::options(cors_preflight_ok))
                 .layer(medium_body_limit)
+                .route("/_rari/config", routing::get(config_dump))
                 .route("/vite-server", routing::get(vite_websocket_proxy))
                 .route("/vite-server/", routing::get(vite_websocket_proxy))
                 .route("/vite-server/{*path}", routing::any(vite_reverse_proxy))
@@ -20,10 +20,12 @@ use crate::{
             CacheHandlerRegistry,
             handler::CacheHandler,
             response::{ResponseCache, StaticFastCache},
+            revalidate::RevalidateRateLimiter,
         },
         config::Config,
         image::ImageOptimizer,
         og::OgImageGenerator,
+        rendering::{hmr_cancel::HmrRenderCancellation, render_queue::RenderScheduler},
         routing::{ApiRouteHandler, AppRouter},
     },
 };
@@ -102,6 +104,9 @@ pub struct ServerState {
     pub image_optimizer: Option<Arc<ImageOptimizer>>,
     pub cache_registry: Arc<CacheHandlerRegistry>,
     pub image_handler: Arc<dyn CacheHandler>,
+    pub render_scheduler: Arc<RenderScheduler>,
+    pub hmr_render_cancellation: Arc<HmrRenderCancellation>,
+    pub revalidate_limiter: Arc<RevalidateRateLimiter>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -1,7 +1,14 @@
-use axum::http::{HeaderMap, HeaderValue};
+use std::net::{IpAddr, SocketAddr};
+
+use axum::{
+    extract::ConnectInfo,
+    http::{Extensions, HeaderMap, HeaderValue},
+};
 use cow_utils::CowUtils;
 use rustc_hash::{FxHashMap, FxHashSet};
 
+use crate::server::config::ServerConfig;
+
 pub const RARI_NAVIGATION_ID_HEADER: &str = "rari-navigation-id";
 
 #[expect(
@@ -265,11 +272,205 @@ pub fn add_api_security_headers(headers: &mut HeaderMap) {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestOrigin {
+    pub scheme: String,
+    pub host: String,
+    pub port: Option<u16>,
+}
+
+impl RequestOrigin {
+    pub fn to_origin_string(&self) -> String {
+        match self.port {
+            Some(port) => format!("{}://{}:{port}", self.scheme, self.host),
+            None => format!("{}://{}", self.scheme, self.host),
+        }
+    }
+}
+
+fn first_forwarded_value<'a>(
+    headers: &'a FxHashMap<String, String>,
+    name: &str,
+) -> Option<&'a str> {
+    headers.get(name).and_then(|v| v.split(',').next()).map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// Address of the connected peer, when the server was started with connect info.
+pub fn peer_ip(extensions: &Extensions) -> Option<IpAddr> {
+    extensions.get::<ConnectInfo<SocketAddr>>().map(|ConnectInfo(addr)| addr.ip())
+}
+
+/// Resolves the client-facing scheme/host/port, honoring `X-Forwarded-*` only
+/// when `peer` is trusted by `server`.
+#[expect(
+    clippy::implicit_hasher,
+    reason = "FxHashMap is the specific hasher needed for this codebase"
+)]
+pub fn resolve_request_origin(
+    headers: &FxHashMap<String, String>,
+    peer: Option<IpAddr>,
+    server: &ServerConfig,
+    default_scheme: &str,
+) -> Option<RequestOrigin> {
+    let trusted = server.trusts_forwarded_from(peer);
+
+    let forwarded = |name: &str| trusted.then(|| first_forwarded_value(headers, name)).flatten();
+
+    let authority =
+        forwarded("x-forwarded-host").or_else(|| first_forwarded_value(headers, "host"))?;
+
+    let scheme = forwarded("x-forwarded-proto")
+        .or_else(|| forwarded("x-forwarded-protocol"))
+        .unwrap_or(default_scheme)
+        .cow_to_ascii_lowercase()
+        .into_owned();
+
+    let (host, authority_port) = match authority.rsplit_once(':') {
+        Some((host, port))
+            if !host.contains(':') || (host.starts_with('[') && host.ends_with(']')) =>
+        {
+            match port.parse::<u16>() {
+                Ok(port) => (host, Some(port)),
+                Err(_) => (authority, None),
+            }
+        }
+        _ => (authority, None),
+    };
+
+    let port = authority_port
+        .or_else(|| forwarded("x-forwarded-port")?.parse().ok())
+        .filter(|port| !matches!((scheme.as_str(), port), ("http", 80) | ("https", 443)));
+
+    Some(RequestOrigin { scheme, host: host.to_string(), port })
+}
+
 #[cfg(test)]
 #[expect(clippy::unwrap_used)]
 mod tests {
     use super::*;
 
+    fn forwarded_headers() -> FxHashMap<String, String> {
+        let mut headers = FxHashMap::default();
+        headers.insert("host".to_string(), "internal:3000".to_string());
+        headers.insert("x-forwarded-host".to_string(), "example.com, proxy.local".to_string());
+        headers.insert("x-forwarded-proto".to_string(), "https".to_string());
+        headers.insert("x-forwarded-port".to_string(), "8443".to_string());
+        headers
+    }
+
+    #[test]
+    fn test_peer_ip_reads_connect_info() {
+        let mut extensions = Extensions::new();
+        assert_eq!(peer_ip(&extensions), None);
+
+        extensions.insert(ConnectInfo("10.0.0.1:52100".parse::<SocketAddr>().unwrap()));
+        assert_eq!(peer_ip(&extensions), Some("10.0.0.1".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_resolve_request_origin_trusted_proxy() {
+        let mut server = ServerConfig::default();
+        server.trusted_proxies = vec!["10.0.0.1".parse().unwrap()];
+
+        let origin = resolve_request_origin(
+            &forwarded_headers(),
+            Some("10.0.0.1".parse().unwrap()),
+            &server,
+            "http",
+        )
+        .unwrap();
+
+        assert_eq!(origin.to_origin_string(), "https://example.com:8443");
+    }
+
+    #[test]
+    fn test_resolve_request_origin_untrusted_peer_ignores_forwarded() {
+        let mut server = ServerConfig::default();
+        server.trusted_proxies = vec!["10.0.0.1".parse().unwrap()];
+
+        let origin = resolve_request_origin(
+            &forwarded_headers(),
+            Some("203.0.113.7".parse().unwrap()),
+            &server,
+            "http",
+        )
+        .unwrap();
+
+        assert_eq!(
+            origin,
+            RequestOrigin {
+                scheme: "http".to_string(),
+                host: "internal".to_string(),
+                port: Some(3000)
+            }
+        );
+    }
+
+    #[test]
+    fn test_resolve_request_origin_default_config_ignores_forwarded() {
+        let origin = resolve_request_origin(
+            &forwarded_headers(),
+            Some("203.0.113.7".parse().unwrap()),
+            &ServerConfig::default(),
+            "http",
+        )
+        .unwrap();
+
+        assert_eq!(origin.to_origin_string(), "http://internal:3000");
+    }
+
+    #[test]
+    fn test_resolve_request_origin_trusted_cidr() {
+        let mut server = ServerConfig::default();
+        server.trusted_proxies = vec!["10.0.0.0/8".parse().unwrap()];
+
+        let origin = resolve_request_origin(
+            &forwarded_headers(),
+            Some("10.1.2.3".parse().unwrap()),
+            &server,
+            "http",
+        )
+        .unwrap();
+
+        assert_eq!(origin.to_origin_string(), "https://example.com:8443");
+    }
+
+    #[test]
+    fn test_resolve_request_origin_forwarding_disabled() {
+        let mut server = ServerConfig::default();
+        server.trusted_proxies = vec!["10.0.0.1".parse().unwrap()];
+        server.trust_forwarded_headers = false;
+
+        let origin = resolve_request_origin(
+            &forwarded_headers(),
+            Some("10.0.0.1".parse().unwrap()),
+            &server,
+            "https",
+        )
+        .unwrap();
+        assert_eq!(origin.to_origin_string(), "https://internal:3000");
+    }
+
+    #[test]
+    fn test_resolve_request_origin_drops_default_port() {
+        let mut headers = FxHashMap::default();
+        headers.insert("host".to_string(), "example.com".to_string());
+        headers.insert("x-forwarded-proto".to_string(), "https".to_string());
+        headers.insert("x-forwarded-port".to_string(), "443".to_string());
+
+        let mut server = ServerConfig::default();
+        server.trusted_proxies = vec!["10.0.0.1".parse().unwrap()];
+
+        let origin =
+            resolve_request_origin(&headers, Some("10.0.0.1".parse().unwrap()), &server, "http")
+                .unwrap();
+        assert_eq!(origin.to_origin_string(), "https://example.com");
+        assert!(
+            resolve_request_origin(&FxHashMap::default(), None, &ServerConfig::default(), "http")
+                .is_none()
+        );
+    }
+
     #[test]
     fn test_is_origin_allowed_exact_match() {
         let allowed = vec!["https://example.com".to_string()];
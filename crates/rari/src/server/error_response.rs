@@ -33,6 +33,30 @@ pub fn json_response(err: &RariError, is_development: bool) -> Response {
         .expect("Valid RariError JSON response")
 }
 
+/// JSON error body for API-style page routes: `{error, code, details?}`.
+/// `details` carries the full error message and is omitted outside development.
+#[must_use]
+pub fn api_json_response(err: &RariError, is_development: bool) -> Response {
+    let mut body = serde_json::json!({
+        "error": err.safe_message(false),
+        "code": err.code(),
+    });
+    if is_development {
+        body["details"] = serde_json::Value::String(err.to_string());
+    }
+
+    #[expect(
+        clippy::expect_used,
+        reason = "Response::builder() with valid status/headers never fails"
+    )]
+    Response::builder()
+        .status(status(err))
+        .header("content-type", "application/json")
+        .header("cache-control", "no-store")
+        .body(Body::from(body.to_string()))
+        .expect("Valid RariError JSON response")
+}
+
 /// Axum error type that renders as a structured JSON [`RariError`] response.
 #[derive(Debug)]
 #[non_exhaustive]
@@ -28,6 +28,12 @@ pub struct ImageConfig {
     pub optimization_concurrency: Option<usize>,
     #[serde(default)]
     pub preoptimize_manifest: Vec<ImageVariant>,
+    /// Maximum concurrent OG image renders; `None` leaves them unbounded.
+    #[serde(default)]
+    pub og_render_concurrency: Option<usize>,
+    /// How long an OG render waits for a slot before answering 503.
+    #[serde(default = "default_og_render_queue_timeout_ms")]
+    pub og_render_queue_timeout_ms: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -72,6 +78,8 @@ impl Default for ImageConfig {
             minimum_cache_ttl: default_minimum_cache_ttl(),
             optimization_concurrency: None,
             preoptimize_manifest: Vec::new(),
+            og_render_concurrency: None,
+            og_render_queue_timeout_ms: default_og_render_queue_timeout_ms(),
         }
     }
 }
@@ -103,3 +111,7 @@ fn default_max_redirects() -> u8 {
 fn default_minimum_cache_ttl() -> u64 {
     60
 }
+
+fn default_og_render_queue_timeout_ms() -> u64 {
+    5000
+}
@@ -2,6 +2,7 @@ use std::{
     env,
     error::Error,
     fs as std_fs, mem,
+    net::IpAddr,
     path::PathBuf,
     sync::{Arc, OnceLock},
     task::{Context, Poll},
@@ -22,7 +23,13 @@ use tower::{Layer, Service};
 
 use crate::{
     runtime::JsExecutionRuntime,
-    server::core::{types::ServerState, utils::component::get_dist_path_for_component},
+    server::core::{
+        types::ServerState,
+        utils::{
+            component::get_dist_path_for_component,
+            http::{peer_ip, resolve_request_origin},
+        },
+    },
     utils::path::path_to_file_url,
 };
 
@@ -153,10 +160,11 @@ async fn execute_proxy(
     method: String,
     uri: String,
     headers: FxHashMap<String, String>,
+    peer: Option<IpAddr>,
 ) -> Result<ProxyResult, RariError> {
-    let scheme = headers.get("x-forwarded-proto").cloned().unwrap_or_else(|| "http".to_string());
-    let host = headers.get("host").cloned().unwrap_or_else(|| "localhost".to_string());
-    let url = format!("{scheme}://{host}{uri}");
+    let origin = resolve_request_origin(&headers, peer, &state.config.server, "http")
+        .map_or_else(|| "http://localhost".to_string(), |origin| origin.to_origin_string());
+    let url = format!("{origin}{uri}");
 
     let request_data = serde_json::json!({
         "url": url,
@@ -235,8 +243,9 @@ where
                 .iter()
                 .filter_map(|(k, v)| v.to_str().ok().map(|v| (k.to_string(), v.to_string())))
                 .collect();
+            let peer = peer_ip(request.extensions());
 
-            match execute_proxy(&state, method, uri, headers).await {
+            match execute_proxy(&state, method, uri, headers, peer).await {
                 Ok(result) => {
                     if let Some(redirect) = result.redirect {
                         let status = if redirect.permanent {
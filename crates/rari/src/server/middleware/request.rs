@@ -95,20 +95,21 @@ pub async fn security_headers_middleware(mut request: Request<Body>, next: Next)
         None
     };
 
+    let path = request.uri().path().to_string();
     let mut response = next.run(request).await;
 
     let status = response.status();
     let headers = response.headers_mut();
 
-    add_security_headers(headers, nonce.as_deref());
+    add_security_headers(headers, &path, nonce.as_deref());
     apply_error_cache_control(headers, status);
 
     response
 }
 
-fn add_security_headers(headers: &mut HeaderMap, nonce: Option<&str>) {
+fn add_security_headers(headers: &mut HeaderMap, path: &str, nonce: Option<&str>) {
     let csp_policy = if let Some(config) = Config::get() {
-        config.build_csp_policy()
+        config.build_csp_policy_for_route(path)
     } else {
         "default-src 'self'; script-src 'self' 'unsafe-inline' 'unsafe-eval'; style-src 'self' 'unsafe-inline'; img-src 'self' data: https:; font-src 'self' data:; connect-src 'self' ws: wss:; frame-ancestors 'self'; base-uri 'self'; form-action 'self'".to_string()
     };
@@ -12,16 +12,17 @@ use serde_json::{Map, Value};
 use tokio::{fs, sync::RwLock, task};
 
 use super::{
-    OgImageError,
+    OgImageError, OgRenderLimiter,
     cache::OgImageCache,
     layout::LayoutEngine,
     rendering::ImageRenderer,
+    resolve_og_dimensions,
     types::{JsxChild, JsxElement, OgImageEntry},
 };
 use crate::{
     runtime::JsExecutionRuntime,
     server::{
-        cache::handler::CacheError, core::utils::component::extract_component_id,
+        cache::handler::CacheError, config::Config, core::utils::component::extract_component_id,
         loader::SERVER_MANIFEST_PATH, routing::types::ParamValue,
     },
     utils::{float, path::path_to_file_url},
@@ -33,6 +34,7 @@ pub struct OgImageGenerator {
     manifest: Arc<RwLock<FxHashMap<String, OgImageEntry>>>,
     project_path: PathBuf,
     server_manifest: Arc<RwLock<FxHashMap<String, String>>>,
+    render_limiter: OgRenderLimiter,
 }
 
 impl OgImageGenerator {
@@ -43,6 +45,7 @@ impl OgImageGenerator {
             manifest: Arc::new(RwLock::new(FxHashMap::default())),
             project_path,
             server_manifest: Arc::new(RwLock::new(FxHashMap::default())),
+            render_limiter: OgRenderLimiter::unlimited(),
         }
     }
 
@@ -57,6 +60,7 @@ impl OgImageGenerator {
             manifest: Arc::new(RwLock::new(FxHashMap::default())),
             project_path,
             server_manifest: Arc::new(RwLock::new(FxHashMap::default())),
+            render_limiter: OgRenderLimiter::unlimited(),
         }
     }
 
@@ -71,9 +75,16 @@ impl OgImageGenerator {
             manifest: Arc::new(RwLock::new(FxHashMap::default())),
             project_path,
             server_manifest: Arc::new(RwLock::new(FxHashMap::default())),
+            render_limiter: OgRenderLimiter::unlimited(),
         }
     }
 
+    #[must_use]
+    pub fn with_render_limiter(mut self, render_limiter: OgRenderLimiter) -> Self {
+        self.render_limiter = render_limiter;
+        self
+    }
+
     #[expect(clippy::missing_errors_doc)]
     pub async fn load_manifest(&self, manifest_path: &str) -> Result<(), OgImageError> {
         let content = fs::read_to_string(manifest_path)
@@ -166,9 +177,6 @@ impl OgImageGenerator {
 
     #[expect(clippy::missing_errors_doc)]
     pub async fn generate(&self, route_path: &str) -> Result<(Vec<u8>, bool), OgImageError> {
-        const MAX_OG_WIDTH: u32 = 2400;
-        const MAX_OG_HEIGHT: u32 = 1260;
-
         if let Some(cached) = self.cache.get(route_path).await {
             return Ok((cached, true));
         }
@@ -181,10 +189,13 @@ impl OgImageGenerator {
         let entry = entry.clone();
         drop(manifest);
 
+        let limits = Config::get().map(|config| config.og_image.clone()).unwrap_or_default();
+        let (width, height) = resolve_og_dimensions(&limits, entry.width, entry.height)?;
+
         let jsx_element = self.execute_og_component(&entry, route_path, &params).await?;
 
-        let width = entry.width.unwrap_or(1200).min(MAX_OG_WIDTH);
-        let height = entry.height.unwrap_or(630).min(MAX_OG_HEIGHT);
+        // Held across layout and rasterization; released when generation ends.
+        let _render_permit = self.render_limiter.acquire().await?;
 
         let webp_data = task::spawn_blocking(move || -> Result<Vec<u8>, OgImageError> {
             let (computed_layout, font_context) = {
@@ -24,6 +24,22 @@ use crate::{
 
 pub struct MeasureContext {
     font_context: RefCell<ParleyFontContext>,
+    text_cache: RefCell<FxHashMap<TextMeasureKey, (f32, f32)>>,
+}
+
+const MAX_TEXT_MEASURE_CACHE_ENTRIES: usize = 4096;
+
+/// Everything that affects a shaped text box; floats are keyed by bit pattern.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct TextMeasureKey {
+    text: String,
+    font_size: u32,
+    font_weight: u16,
+    font_family: Option<String>,
+    letter_spacing: Option<String>,
+    text_transform: Option<String>,
+    white_space: style::WhiteSpace,
+    max_width: Option<u32>,
 }
 
 pub struct LayoutEngine {
@@ -36,6 +52,7 @@ pub struct NodeData {
     pub element: JsxElement,
     pub style: FxHashMap<String, String>,
     pub has_text: bool,
+    pub white_space: style::WhiteSpace,
 }
 
 impl LayoutEngine {
@@ -46,6 +63,7 @@ impl LayoutEngine {
             taffy: TaffyTree::new(),
             measure_context: MeasureContext {
                 font_context: RefCell::new(font_context.inner.clone()),
+                text_cache: RefCell::new(FxHashMap::default()),
             },
             font_context,
         }
@@ -64,7 +82,7 @@ impl LayoutEngine {
         self.taffy.clear();
 
         let mut inherited_color = None;
-        let root_node = self.build_tree(element, &mut inherited_color)?;
+        let root_node = self.build_tree(element, &mut inherited_color, None)?;
 
         self.taffy
             .compute_layout_with_measure(
@@ -91,9 +109,20 @@ impl LayoutEngine {
         &mut self,
         element: &JsxElement,
         inherited_color: &mut Option<String>,
+        inherited_white_space: Option<&str>,
     ) -> Result<NodeId, RariError> {
         let mut style = Self::parse_style(&element.props);
 
+        if !style.contains_key("whiteSpace")
+            && let Some(white_space) = inherited_white_space
+        {
+            style.insert("whiteSpace".to_string(), white_space.to_string());
+        }
+        let white_space = style
+            .get("whiteSpace")
+            .map(|value| style::WhiteSpace::parse(value))
+            .unwrap_or_default();
+
         if !style.contains_key("color") {
             if let Some(color) = inherited_color {
                 style.insert("color".to_string(), color.clone());
@@ -105,7 +134,8 @@ impl LayoutEngine {
         let has_text = Self::has_text_content(element);
         let taffy_style = Self::style_to_taffy(&style);
 
-        let node_data = NodeData { element: element.clone(), style: style.clone(), has_text };
+        let node_data =
+            NodeData { element: element.clone(), style: style.clone(), has_text, white_space };
 
         if is_svg_element(&element.element_type) {
             let node = self.taffy.new_leaf_with_context(taffy_style, node_data).map_err(|e| {
@@ -117,7 +147,15 @@ impl LayoutEngine {
         let mut child_nodes = Vec::new();
         for child in &element.children {
             if let JsxChild::Element(child_element) = child {
-                let child_node = self.build_tree(child_element, inherited_color)?;
+                // A `<br>` beside text is a line break in that text, not a box.
+                if has_text && child_element.element_type == "br" {
+                    continue;
+                }
+                let child_node = self.build_tree(
+                    child_element,
+                    inherited_color,
+                    style.get("whiteSpace").map(String::as_str),
+                )?;
                 child_nodes.push(child_node);
             }
         }
@@ -404,6 +442,16 @@ impl LayoutEngine {
             height: layout.size.height,
             border: layout.border,
             padding: layout.padding,
+            color: node_data.style.get("color").and_then(|c| style::parse_color(c)),
+            background_color: node_data
+                .style
+                .get("backgroundColor")
+                .and_then(|c| style::parse_color(c)),
+            text_shadow: node_data
+                .style
+                .get("textShadow")
+                .and_then(|s| style::TextShadow::parse(s)),
+            white_space: node_data.white_space,
             element: node_data.element.clone(),
             style: node_data.style.clone(),
             children,
@@ -462,18 +510,7 @@ fn measure_node(
         return Size::ZERO;
     }
 
-    #[expect(clippy::match_wildcard_for_single_variants)]
-    let text: String = node_data
-        .element
-        .children
-        .iter()
-        .filter_map(|child| match child {
-            JsxChild::Text(t) => Some(t.as_str()),
-            _ => None,
-        })
-        .collect::<Vec<_>>()
-        .join("");
-
+    let text = element_text(&node_data.element, node_data.white_space);
     if text.is_empty() {
         return Size::ZERO;
     }
@@ -498,14 +535,24 @@ fn measure_node(
         })
         .unwrap_or(400);
 
-    let max_width = known_dimensions.width.or(match available_space.width {
-        AvailableSpace::Definite(w) => Some(w),
-        AvailableSpace::MaxContent => None,
-        AvailableSpace::MinContent => Some(0.0),
-    });
-
-    let (text_width, text_height) =
-        measure_text_with_parley(&context.font_context, &text, font_size, font_weight, max_width);
+    let max_width =
+        node_data.white_space.wrap_width(known_dimensions.width.or(match available_space.width {
+            AvailableSpace::Definite(w) => Some(w),
+            AvailableSpace::MaxContent => None,
+            AvailableSpace::MinContent => Some(0.0),
+        }));
+
+    let key = TextMeasureKey {
+        text,
+        font_size: font_size.to_bits(),
+        font_weight,
+        font_family: node_data.style.get("fontFamily").cloned(),
+        letter_spacing: node_data.style.get("letterSpacing").cloned(),
+        text_transform: node_data.style.get("textTransform").cloned(),
+        white_space: node_data.white_space,
+        max_width: max_width.map(f32::to_bits),
+    };
+    let (text_width, text_height) = measure_text_cached(context, key, font_size, max_width);
 
     Size {
         width: known_dimensions.width.unwrap_or(text_width),
@@ -513,6 +560,30 @@ fn measure_node(
     }
 }
 
+/// Text of `element`'s direct text children as it should be shaped. Each
+/// `<br>` child ends a segment; segments are prepared separately and joined
+/// with `\n` so the break survives whitespace collapsing.
+pub(crate) fn element_text(element: &JsxElement, white_space: style::WhiteSpace) -> String {
+    let mut segments = vec![String::new()];
+    for child in &element.children {
+        match child {
+            JsxChild::Text(text) => {
+                if let Some(segment) = segments.last_mut() {
+                    segment.push_str(text);
+                }
+            }
+            JsxChild::Element(child) if child.element_type == "br" => segments.push(String::new()),
+            JsxChild::Element(_) => {}
+        }
+    }
+
+    let segments: Vec<_> = segments.iter().map(|segment| white_space.prepare(segment)).collect();
+    if segments.iter().all(|segment| segment.is_empty()) {
+        return String::new();
+    }
+    segments.join("\n")
+}
+
 fn measure_image(
     node_data: &NodeData,
     known_dimensions: Size<Option<f32>>,
@@ -619,7 +690,6 @@ fn load_image_dimensions(src: &str) -> Option<Size<f32>> {
             height: float::u32_to_f32(img.height()),
         })
     } else if src.starts_with("data:") {
-        use base64::{Engine as _, engine::general_purpose};
         let parts: Vec<&str> = src.splitn(2, ',').collect();
         if parts.len() != 2 {
             return None;
@@ -629,7 +699,13 @@ fn load_image_dimensions(src: &str) -> Option<Size<f32>> {
         let data = parts[1];
 
         if header.contains("base64") {
-            let decoded = general_purpose::STANDARD.decode(data).ok()?;
+            let decoded = match super::decode_data_uri_payload(data) {
+                Ok(decoded) => decoded,
+                Err(e) => {
+                    tracing::warn!("Ignoring OG data URI image: {e}");
+                    return None;
+                }
+            };
             let img = image::load_from_memory(&decoded).ok()?;
             Some(Size {
                 width: float::u32_to_f32(img.width()),
@@ -647,6 +723,32 @@ fn load_image_dimensions(src: &str) -> Option<Size<f32>> {
     }
 }
 
+fn measure_text_cached(
+    context: &MeasureContext,
+    key: TextMeasureKey,
+    font_size: f32,
+    max_width: Option<f32>,
+) -> (f32, f32) {
+    if let Some(size) = context.text_cache.borrow().get(&key) {
+        return *size;
+    }
+
+    let size = measure_text_with_parley(
+        &context.font_context,
+        &key.text,
+        font_size,
+        key.font_weight,
+        max_width,
+    );
+
+    let mut cache = context.text_cache.borrow_mut();
+    if cache.len() >= MAX_TEXT_MEASURE_CACHE_ENTRIES {
+        cache.clear();
+    }
+    cache.insert(key, size);
+    size
+}
+
 fn measure_text_with_parley(
     font_context: &RefCell<ParleyFontContext>,
     text: &str,
@@ -685,7 +787,109 @@ pub struct ComputedLayout {
     pub height: f32,
     pub border: taffy::Rect<f32>,
     pub padding: taffy::Rect<f32>,
+    pub color: Option<image::Rgba<u8>>,
+    pub background_color: Option<image::Rgba<u8>>,
+    pub text_shadow: Option<style::TextShadow>,
+    pub white_space: style::WhiteSpace,
     pub element: JsxElement,
     pub style: FxHashMap<String, String>,
     pub children: Vec<Self>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measure_key(text: &str, max_width: Option<f32>) -> TextMeasureKey {
+        TextMeasureKey {
+            text: text.to_string(),
+            font_size: 24.0_f32.to_bits(),
+            font_weight: 700,
+            font_family: None,
+            letter_spacing: None,
+            text_transform: None,
+            white_space: style::WhiteSpace::Normal,
+            max_width: max_width.map(f32::to_bits),
+        }
+    }
+
+    #[test]
+    fn test_text_measure_cache_hit_returns_identical_size() {
+        let context = MeasureContext {
+            font_context: RefCell::new(ParleyFontContext::new()),
+            text_cache: RefCell::new(FxHashMap::default()),
+        };
+
+        let first =
+            measure_text_cached(&context, measure_key("Hello OG", Some(300.0)), 24.0, Some(300.0));
+        assert_eq!(context.text_cache.borrow().len(), 1);
+
+        let second =
+            measure_text_cached(&context, measure_key("Hello OG", Some(300.0)), 24.0, Some(300.0));
+        assert_eq!(first, second);
+        assert_eq!(context.text_cache.borrow().len(), 1);
+
+        measure_text_cached(&context, measure_key("Hello OG", Some(100.0)), 24.0, Some(100.0));
+        assert_eq!(context.text_cache.borrow().len(), 2);
+    }
+
+    fn text_node(text: &str, white_space: style::WhiteSpace) -> NodeData {
+        NodeData {
+            element: JsxElement {
+                element_type: "div".to_string(),
+                props: Value::Null,
+                children: vec![JsxChild::Text(text.to_string())],
+            },
+            style: FxHashMap::default(),
+            has_text: true,
+            white_space,
+        }
+    }
+
+    fn measure(node: &mut NodeData, width: f32) -> Size<f32> {
+        let context = MeasureContext {
+            font_context: RefCell::new(ParleyFontContext::new()),
+            text_cache: RefCell::new(FxHashMap::default()),
+        };
+        measure_node(
+            &context,
+            Size { width: None, height: None },
+            Size { width: AvailableSpace::Definite(width), height: AvailableSpace::MaxContent },
+            Some(node),
+        )
+    }
+
+    #[test]
+    fn test_nowrap_measures_wider_than_box() {
+        let text = "A forced single line label that cannot fit";
+        let wrapped = measure(&mut text_node(text, style::WhiteSpace::Normal), 80.0);
+        let single = measure(&mut text_node(text, style::WhiteSpace::NoWrap), 80.0);
+
+        assert!(single.width > 80.0);
+        assert!(single.height < wrapped.height);
+    }
+
+    #[test]
+    fn test_br_splits_text_into_lines() {
+        let br = JsxChild::Element(Box::new(JsxElement {
+            element_type: "br".to_string(),
+            props: Value::Null,
+            children: Vec::new(),
+        }));
+        let mut node = text_node("Line1", style::WhiteSpace::Normal);
+        node.element.children.extend([br, JsxChild::Text("Line2".to_string())]);
+        assert_eq!(element_text(&node.element, node.white_space), "Line1\nLine2");
+
+        let single = measure(&mut text_node("Line1", style::WhiteSpace::Normal), 1000.0);
+        let broken = measure(&mut node, 1000.0);
+        assert!(broken.height > single.height * 1.5);
+    }
+
+    #[test]
+    fn test_pre_honors_explicit_newline() {
+        let normal = measure(&mut text_node("first\nsecond", style::WhiteSpace::Normal), 1000.0);
+        let pre = measure(&mut text_node("first\nsecond", style::WhiteSpace::Pre), 1000.0);
+
+        assert!(pre.height > normal.height);
+    }
+}
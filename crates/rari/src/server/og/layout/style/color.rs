@@ -0,0 +1,224 @@
+use image::Rgba;
+
+use crate::utils::cast;
+
+pub fn parse_color(color_str: &str) -> Option<Rgba<u8>> {
+    let color_str = color_str.trim();
+    let lower = color_str.to_ascii_lowercase();
+
+    if let Some(hex) = lower.strip_prefix('#') {
+        return parse_hex(hex);
+    }
+
+    if let Some(inner) = strip_function(&lower, "rgba").or_else(|| strip_function(&lower, "rgb")) {
+        return parse_rgb_args(inner);
+    }
+
+    if let Some(inner) = strip_function(&lower, "hsla").or_else(|| strip_function(&lower, "hsl")) {
+        return parse_hsl_args(inner);
+    }
+
+    named_color(&lower).map(Rgba)
+}
+
+fn strip_function<'a>(value: &'a str, name: &str) -> Option<&'a str> {
+    value.strip_prefix(name)?.trim_start().strip_prefix('(')?.strip_suffix(')')
+}
+
+fn parse_hex(hex: &str) -> Option<Rgba<u8>> {
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let nibble = |i: usize| u8::from_str_radix(&hex[i..=i], 16).ok().map(|v| v * 17);
+    let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+
+    let rgba = match hex.len() {
+        3 => [nibble(0)?, nibble(1)?, nibble(2)?, 255],
+        4 => [nibble(0)?, nibble(1)?, nibble(2)?, nibble(3)?],
+        6 => [byte(0)?, byte(2)?, byte(4)?, 255],
+        8 => [byte(0)?, byte(2)?, byte(4)?, byte(6)?],
+        _ => return None,
+    };
+
+    Some(Rgba(rgba))
+}
+
+/// Splits both the legacy comma syntax and the space syntax with an optional
+/// `/ alpha` suffix into exactly three channel arguments and an optional alpha.
+fn split_args(inner: &str) -> Option<([&str; 3], Option<&str>)> {
+    let (channels, slash_alpha) = match inner.split_once('/') {
+        Some((channels, alpha)) => (channels, Some(alpha.trim())),
+        None => (inner, None),
+    };
+
+    let parts: Vec<&str> = if channels.contains(',') {
+        channels.split(',').map(str::trim).collect()
+    } else {
+        channels.split_whitespace().collect()
+    };
+
+    match (parts.as_slice(), slash_alpha) {
+        ([a, b, c], alpha) => Some(([a, b, c], alpha)),
+        ([a, b, c, alpha], None) => Some(([a, b, c], Some(alpha))),
+        _ => None,
+    }
+}
+
+fn parse_alpha(value: Option<&str>) -> Option<u8> {
+    let Some(value) = value else {
+        return Some(255);
+    };
+
+    let alpha = if let Some(pct) = value.strip_suffix('%') {
+        pct.trim().parse::<f32>().ok()? / 100.0
+    } else {
+        value.parse::<f32>().ok()?
+    };
+
+    Some(cast::f32_to_u8((alpha.clamp(0.0, 1.0) * 255.0).round()))
+}
+
+fn parse_rgb_channel(value: &str) -> Option<u8> {
+    let channel = if let Some(pct) = value.strip_suffix('%') {
+        pct.trim().parse::<f32>().ok()? * 2.55
+    } else {
+        value.parse::<f32>().ok()?
+    };
+
+    Some(cast::f32_to_u8(channel.clamp(0.0, 255.0).round()))
+}
+
+fn parse_rgb_args(inner: &str) -> Option<Rgba<u8>> {
+    let ([r, g, b], alpha) = split_args(inner)?;
+
+    Some(Rgba([
+        parse_rgb_channel(r)?,
+        parse_rgb_channel(g)?,
+        parse_rgb_channel(b)?,
+        parse_alpha(alpha)?,
+    ]))
+}
+
+fn parse_hue(value: &str) -> Option<f32> {
+    let degrees = if let Some(deg) = value.strip_suffix("deg") {
+        deg.trim().parse::<f32>().ok()?
+    } else if let Some(turn) = value.strip_suffix("turn") {
+        turn.trim().parse::<f32>().ok()? * 360.0
+    } else if let Some(rad) = value.strip_suffix("rad") {
+        rad.trim().parse::<f32>().ok()?.to_degrees()
+    } else {
+        value.parse::<f32>().ok()?
+    };
+
+    Some(degrees.rem_euclid(360.0))
+}
+
+fn parse_percentage(value: &str) -> Option<f32> {
+    let pct = value.strip_suffix('%').unwrap_or(value).trim().parse::<f32>().ok()?;
+    Some((pct / 100.0).clamp(0.0, 1.0))
+}
+
+fn parse_hsl_args(inner: &str) -> Option<Rgba<u8>> {
+    let ([h, s, l], alpha) = split_args(inner)?;
+    let hue = parse_hue(h)?;
+    let saturation = parse_percentage(s)?;
+    let lightness = parse_percentage(l)?;
+
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let sector = hue / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r1, g1, b1) = match sector {
+        s if s < 1.0 => (chroma, x, 0.0),
+        s if s < 2.0 => (x, chroma, 0.0),
+        s if s < 3.0 => (0.0, chroma, x),
+        s if s < 4.0 => (0.0, x, chroma),
+        s if s < 5.0 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    let to_u8 = |v: f32| cast::f32_to_u8(((v + m) * 255.0).round().clamp(0.0, 255.0));
+
+    Some(Rgba([to_u8(r1), to_u8(g1), to_u8(b1), parse_alpha(alpha)?]))
+}
+
+fn named_color(name: &str) -> Option<[u8; 4]> {
+    let rgba = match name {
+        "black" => [0, 0, 0, 255],
+        "white" => [255, 255, 255, 255],
+        "red" => [255, 0, 0, 255],
+        "green" => [0, 128, 0, 255],
+        "lime" => [0, 255, 0, 255],
+        "blue" => [0, 0, 255, 255],
+        "yellow" => [255, 255, 0, 255],
+        "cyan" | "aqua" => [0, 255, 255, 255],
+        "magenta" | "fuchsia" => [255, 0, 255, 255],
+        "gray" | "grey" => [128, 128, 128, 255],
+        "transparent" => [0, 0, 0, 0],
+        "orange" => [255, 165, 0, 255],
+        "purple" => [128, 0, 128, 255],
+        "pink" => [255, 192, 203, 255],
+        "brown" => [165, 42, 42, 255],
+        "indigo" => [75, 0, 130, 255],
+        "violet" => [238, 130, 238, 255],
+        "navy" => [0, 0, 128, 255],
+        "teal" => [0, 128, 128, 255],
+        "olive" => [128, 128, 0, 255],
+        "maroon" => [128, 0, 0, 255],
+        "silver" => [192, 192, 192, 255],
+        _ => return None,
+    };
+
+    Some(rgba)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(parse_color("#f00"), Some(Rgba([255, 0, 0, 255])));
+        assert_eq!(parse_color("#f008"), Some(Rgba([255, 0, 0, 136])));
+        assert_eq!(parse_color("#1a2B3c"), Some(Rgba([26, 43, 60, 255])));
+        assert_eq!(parse_color("#1a2b3c80"), Some(Rgba([26, 43, 60, 128])));
+        assert_eq!(parse_color("#12345"), None);
+        assert_eq!(parse_color("#zzz"), None);
+    }
+
+    #[test]
+    fn test_parse_rgb() {
+        assert_eq!(parse_color("rgb(10, 20, 30)"), Some(Rgba([10, 20, 30, 255])));
+        assert_eq!(parse_color("rgb(10 20 30)"), Some(Rgba([10, 20, 30, 255])));
+        assert_eq!(parse_color("rgb(100%, 0%, 50%)"), Some(Rgba([255, 0, 128, 255])));
+        assert_eq!(parse_color("rgb(1, 2)"), None);
+    }
+
+    #[test]
+    fn test_parse_rgba_alpha() {
+        assert_eq!(parse_color("rgba(10, 20, 30, 0.5)"), Some(Rgba([10, 20, 30, 128])));
+        assert_eq!(parse_color("rgb(10 20 30 / 25%)"), Some(Rgba([10, 20, 30, 64])));
+        assert_eq!(parse_color("rgba(0, 0, 0, 0)"), Some(Rgba([0, 0, 0, 0])));
+    }
+
+    #[test]
+    fn test_parse_hsl() {
+        assert_eq!(parse_color("hsl(0, 100%, 50%)"), Some(Rgba([255, 0, 0, 255])));
+        assert_eq!(parse_color("hsl(120deg 100% 25%)"), Some(Rgba([0, 128, 0, 255])));
+        assert_eq!(parse_color("hsl(240, 100%, 50%)"), Some(Rgba([0, 0, 255, 255])));
+        assert_eq!(parse_color("hsl(0, 0%, 100%)"), Some(Rgba([255, 255, 255, 255])));
+    }
+
+    #[test]
+    fn test_parse_hsla_alpha() {
+        assert_eq!(parse_color("hsla(0, 100%, 50%, 0.5)"), Some(Rgba([255, 0, 0, 128])));
+        assert_eq!(parse_color("hsl(0.5turn 100% 50% / 1)"), Some(Rgba([0, 255, 255, 255])));
+    }
+
+    #[test]
+    fn test_parse_named() {
+        assert_eq!(parse_color("White"), Some(Rgba([255, 255, 255, 255])));
+        assert_eq!(parse_color("transparent"), Some(Rgba([0, 0, 0, 0])));
+        assert_eq!(parse_color("rebeccapurple-ish"), None);
+    }
+}
@@ -1,5 +1,6 @@
 use image::Rgba;
 
+use super::color::parse_color;
 use crate::utils::{cast, float};
 
 #[derive(Debug, Clone, Copy)]
@@ -130,14 +131,15 @@ impl LinearGradient {
     }
 
     fn parse_color_stop(stop_str: &str) -> Option<ColorStop> {
-        let parts: Vec<&str> = stop_str.split_whitespace().collect();
-        if parts.is_empty() {
-            return None;
-        }
+        let stop_str = stop_str.trim();
+        let (color_str, rest) = match stop_str.rfind(')') {
+            Some(idx) => stop_str.split_at(idx + 1),
+            None => stop_str.split_once(char::is_whitespace).unwrap_or((stop_str, "")),
+        };
 
-        let color = Self::parse_color(parts[0])?;
+        let color = parse_color(color_str)?;
 
-        let position = if parts.len() > 1 { Self::parse_position(parts[1]) } else { None };
+        let position = rest.split_whitespace().next().and_then(Self::parse_position);
 
         Some(ColorStop { color, position })
     }
@@ -152,85 +154,6 @@ impl LinearGradient {
         }
     }
 
-    fn parse_color(color_str: &str) -> Option<Rgba<u8>> {
-        let color_str = color_str.trim();
-
-        let rgba = match color_str {
-            "black" => [0, 0, 0, 255],
-            "white" => [255, 255, 255, 255],
-            "red" => [255, 0, 0, 255],
-            "green" | "lime" => [0, 255, 0, 255],
-            "blue" => [0, 0, 255, 255],
-            "yellow" => [255, 255, 0, 255],
-            "cyan" => [0, 255, 255, 255],
-            "magenta" => [255, 0, 255, 255],
-            "gray" | "grey" => [128, 128, 128, 255],
-            "transparent" => [0, 0, 0, 0],
-            "orange" => [255, 165, 0, 255],
-            "purple" => [128, 0, 128, 255],
-            "pink" => [255, 192, 203, 255],
-            "brown" => [165, 42, 42, 255],
-            "indigo" => [75, 0, 130, 255],
-            "violet" => [238, 130, 238, 255],
-            "navy" => [0, 0, 128, 255],
-            "teal" => [0, 128, 128, 255],
-            "olive" => [128, 128, 0, 255],
-            "maroon" => [128, 0, 0, 255],
-            "silver" => [192, 192, 192, 255],
-            _ => {
-                if color_str.starts_with('#') {
-                    let hex = color_str.trim_start_matches('#');
-                    if hex.len() == 6 {
-                        let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
-                        let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
-                        let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
-                        [r, g, b, 255]
-                    } else if hex.len() == 3 {
-                        let r = u8::from_str_radix(&hex[0..1], 16).ok()? * 17;
-                        let g = u8::from_str_radix(&hex[1..2], 16).ok()? * 17;
-                        let b = u8::from_str_radix(&hex[2..3], 16).ok()? * 17;
-                        [r, g, b, 255]
-                    } else if hex.len() == 8 {
-                        let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
-                        let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
-                        let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
-                        let a = u8::from_str_radix(&hex[6..8], 16).ok()?;
-                        [r, g, b, a]
-                    } else {
-                        return None;
-                    }
-                } else if color_str.starts_with("rgb(") {
-                    let inner = color_str.strip_prefix("rgb(")?.strip_suffix(")")?;
-                    let parts: Vec<&str> = inner.split(',').map(str::trim).collect();
-                    if parts.len() == 3 {
-                        let r = parts[0].parse().ok()?;
-                        let g = parts[1].parse().ok()?;
-                        let b = parts[2].parse().ok()?;
-                        [r, g, b, 255]
-                    } else {
-                        return None;
-                    }
-                } else if color_str.starts_with("rgba(") {
-                    let inner = color_str.strip_prefix("rgba(")?.strip_suffix(")")?;
-                    let parts: Vec<&str> = inner.split(',').map(str::trim).collect();
-                    if parts.len() == 4 {
-                        let r = parts[0].parse().ok()?;
-                        let g = parts[1].parse().ok()?;
-                        let b = parts[2].parse().ok()?;
-                        let a = cast::f32_to_u8(parts[3].parse::<f32>().ok()? * 255.0);
-                        [r, g, b, a]
-                    } else {
-                        return None;
-                    }
-                } else {
-                    return None;
-                }
-            }
-        };
-
-        Some(Rgba(rgba))
-    }
-
     fn resolve_stops(&self, axis_length: f32) -> Vec<ResolvedStop> {
         if self.stops.is_empty() {
             return vec![];
@@ -394,6 +317,16 @@ mod tests {
         assert_eq!(grad.stops[1].color, Rgba([0, 0, 255, 255]));
     }
 
+    #[test]
+    fn test_parse_function_color_stops() {
+        let grad =
+            LinearGradient::parse("linear-gradient(rgba(255, 0, 0, 0.5) 10%, hsl(240, 100%, 50%))")
+                .unwrap();
+        assert_eq!(grad.stops[0].color, Rgba([255, 0, 0, 128]));
+        assert!(matches!(grad.stops[0].position, Some(StopPosition::Percentage(p)) if p == 10.0));
+        assert_eq!(grad.stops[1].color, Rgba([0, 0, 255, 255]));
+    }
+
     #[test]
     fn test_parse_with_positions() {
         let grad = LinearGradient::parse("linear-gradient(to right, red 0%, blue 100%)").unwrap();
@@ -464,7 +397,7 @@ mod tests {
     #[test]
     fn test_parse_rgba() {
         let grad = LinearGradient::parse("linear-gradient(rgba(255,0,0,0.5), blue)").unwrap();
-        assert_eq!(grad.stops[0].color, Rgba([255, 0, 0, 127]));
+        assert_eq!(grad.stops[0].color, Rgba([255, 0, 0, 128]));
         assert_eq!(grad.stops[1].color, Rgba([0, 0, 255, 255]));
     }
 
@@ -1,3 +1,9 @@
+pub mod color;
 pub mod gradient;
+pub mod shadow;
+pub mod white_space;
 
+pub use color::parse_color;
 pub use gradient::LinearGradient;
+pub use shadow::TextShadow;
+pub use white_space::WhiteSpace;
@@ -0,0 +1,111 @@
+use image::Rgba;
+
+use super::color::parse_color;
+
+/// A single CSS `text-shadow`: `offsetX offsetY [blur] [color]`, in either order
+/// of color and lengths. Only the first shadow of a comma list is kept.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextShadow {
+    pub offset_x: f32,
+    pub offset_y: f32,
+    pub blur: f32,
+    pub color: Option<Rgba<u8>>,
+}
+
+impl TextShadow {
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value.is_empty() || value.eq_ignore_ascii_case("none") {
+            return None;
+        }
+
+        let first = split_top_level(value, ',').into_iter().next()?;
+
+        let mut lengths = Vec::new();
+        let mut color = None;
+        for token in split_top_level(first, ' ') {
+            if let Some(length) = parse_length(token) {
+                lengths.push(length);
+            } else if color.is_none() {
+                color = Some(parse_color(token)?);
+            } else {
+                return None;
+            }
+        }
+
+        let (offset_x, offset_y, blur) = match lengths.as_slice() {
+            [x, y] => (*x, *y, 0.0),
+            [x, y, blur] if *blur >= 0.0 => (*x, *y, *blur),
+            _ => return None,
+        };
+
+        Some(Self { offset_x, offset_y, blur, color })
+    }
+}
+
+fn parse_length(token: &str) -> Option<f32> {
+    token.strip_suffix("px").unwrap_or(token).parse::<f32>().ok()
+}
+
+/// Splits on `separator` outside parentheses, dropping empty pieces.
+fn split_top_level(value: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (idx, ch) in value.char_indices() {
+        match ch {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ if ch == separator && depth == 0 => {
+                parts.push(value[start..idx].trim());
+                start = idx + ch.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(value[start..].trim());
+
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_text_shadow_with_rgba_color() {
+        assert_eq!(
+            TextShadow::parse("1px 1px 2px rgba(0,0,0,0.5)"),
+            Some(TextShadow {
+                offset_x: 1.0,
+                offset_y: 1.0,
+                blur: 2.0,
+                color: Some(Rgba([0, 0, 0, 128])),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_text_shadow_color_first_and_no_blur() {
+        let shadow = TextShadow::parse("rgba(0, 0, 0, 0.5) -2px 3px").expect("shadow");
+        assert_eq!((shadow.offset_x, shadow.offset_y, shadow.blur), (-2.0, 3.0, 0.0));
+        assert_eq!(shadow.color, Some(Rgba([0, 0, 0, 128])));
+    }
+
+    #[test]
+    fn test_parse_text_shadow_keeps_first_of_list() {
+        let shadow = TextShadow::parse("1px 2px red, 3px 4px blue").expect("shadow");
+        assert_eq!((shadow.offset_x, shadow.offset_y), (1.0, 2.0));
+        assert_eq!(shadow.color, Some(Rgba([255, 0, 0, 255])));
+    }
+
+    #[test]
+    fn test_parse_text_shadow_rejects_invalid() {
+        assert_eq!(TextShadow::parse("none"), None);
+        assert_eq!(TextShadow::parse("1px"), None);
+        assert_eq!(TextShadow::parse("1px 1px -2px black"), None);
+        assert_eq!(TextShadow::parse("1px 1px nonsense"), None);
+    }
+}
@@ -0,0 +1,77 @@
+use std::borrow::Cow;
+
+/// CSS `white-space` modes the OG text pipeline understands. Measurement and
+/// painting both go through [`WhiteSpace::prepare`] and
+/// [`WhiteSpace::wrap_width`] so they always agree on line breaks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum WhiteSpace {
+    /// Collapse whitespace runs, newlines included, and wrap to the box.
+    #[default]
+    Normal,
+    /// Collapse whitespace and keep everything on one line.
+    NoWrap,
+    /// Keep whitespace and newlines as written; break only at `\n`.
+    Pre,
+    /// Keep whitespace and newlines, and also wrap to the box.
+    PreWrap,
+}
+
+impl WhiteSpace {
+    pub fn parse(value: &str) -> Self {
+        match value.trim() {
+            "nowrap" => Self::NoWrap,
+            "pre" => Self::Pre,
+            "pre-wrap" | "pre-line" | "break-spaces" => Self::PreWrap,
+            _ => Self::Normal,
+        }
+    }
+
+    /// Text as it should be shaped under this mode.
+    pub fn prepare(self, text: &str) -> Cow<'_, str> {
+        match self {
+            Self::Normal | Self::NoWrap => {
+                let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                let keep_leading = text.starts_with(char::is_whitespace) && !collapsed.is_empty();
+                let keep_trailing = text.ends_with(char::is_whitespace) && !collapsed.is_empty();
+                match (keep_leading, keep_trailing) {
+                    (false, false) if collapsed == text => Cow::Borrowed(text),
+                    (false, false) => Cow::Owned(collapsed),
+                    (leading, trailing) => Cow::Owned(format!(
+                        "{}{collapsed}{}",
+                        if leading { " " } else { "" },
+                        if trailing { " " } else { "" }
+                    )),
+                }
+            }
+            Self::Pre | Self::PreWrap => Cow::Borrowed(text),
+        }
+    }
+
+    /// Line-breaking width to use for a box of `max_width`.
+    pub fn wrap_width(self, max_width: Option<f32>) -> Option<f32> {
+        match self {
+            Self::Normal | Self::PreWrap => max_width,
+            Self::NoWrap | Self::Pre => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_white_space() {
+        assert_eq!(WhiteSpace::parse("nowrap"), WhiteSpace::NoWrap);
+        assert_eq!(WhiteSpace::parse("pre"), WhiteSpace::Pre);
+        assert_eq!(WhiteSpace::parse("pre-wrap"), WhiteSpace::PreWrap);
+        assert_eq!(WhiteSpace::parse("bogus"), WhiteSpace::Normal);
+    }
+
+    #[test]
+    fn test_prepare_collapses_unless_pre() {
+        assert_eq!(WhiteSpace::Normal.prepare("a \n  b"), "a b");
+        assert_eq!(WhiteSpace::NoWrap.prepare("Hello "), "Hello ");
+        assert_eq!(WhiteSpace::Pre.prepare("a \n  b"), "a \n  b");
+    }
+}
@@ -0,0 +1,98 @@
+use std::{sync::Arc, time::Duration};
+
+use tokio::{
+    sync::{OwnedSemaphorePermit, Semaphore},
+    time,
+};
+
+use super::OgImageError;
+
+/// Caps concurrent OG layout and rasterization. Requests beyond the limit wait
+/// up to `max_wait` for a slot and are then refused with
+/// [`OgImageError::Busy`]. With no limit configured every request proceeds.
+#[derive(Clone)]
+pub struct OgRenderLimiter {
+    semaphore: Option<Arc<Semaphore>>,
+    max_wait: Duration,
+}
+
+impl OgRenderLimiter {
+    #[must_use]
+    pub fn new(concurrency: Option<usize>, max_wait: Duration) -> Self {
+        let semaphore = concurrency.map(|limit| {
+            if limit == 0 {
+                tracing::warn!("og_render_concurrency is 0, clamping to 1");
+            }
+            Arc::new(Semaphore::new(limit.max(1)))
+        });
+        Self { semaphore, max_wait }
+    }
+
+    #[must_use]
+    pub fn unlimited() -> Self {
+        Self { semaphore: None, max_wait: Duration::ZERO }
+    }
+
+    pub async fn acquire(&self) -> Result<Option<OwnedSemaphorePermit>, OgImageError> {
+        let Some(semaphore) = &self.semaphore else {
+            return Ok(None);
+        };
+
+        match time::timeout(self.max_wait, Arc::clone(semaphore).acquire_owned()).await {
+            Ok(Ok(permit)) => Ok(Some(permit)),
+            Ok(Err(_)) => Err(OgImageError::InternalError("OG render limiter closed".to_string())),
+            Err(_) => Err(OgImageError::Busy(format!(
+                "no OG render slot became free within {} ms",
+                self.max_wait.as_millis()
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_concurrency_stays_within_limit() {
+        let limiter = OgRenderLimiter::new(Some(2), Duration::from_secs(5));
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..8)
+            .map(|_| {
+                let limiter = limiter.clone();
+                let running = Arc::clone(&running);
+                let peak = Arc::clone(&peak);
+                tokio::spawn(async move {
+                    let _permit = limiter.acquire().await;
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    time::sleep(Duration::from_millis(10)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+
+        for task in tasks {
+            let _ = task.await;
+        }
+
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+    }
+
+    #[tokio::test]
+    async fn test_full_queue_returns_busy() {
+        let limiter = OgRenderLimiter::new(Some(1), Duration::from_millis(20));
+        let held = limiter.acquire().await;
+        assert!(matches!(held, Ok(Some(_))));
+
+        let refused = limiter.acquire().await;
+        assert!(matches!(refused, Err(OgImageError::Busy(_))));
+
+        drop(held);
+        assert!(matches!(limiter.acquire().await, Ok(Some(_))));
+    }
+}
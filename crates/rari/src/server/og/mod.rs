@@ -3,6 +3,7 @@
 mod cache;
 mod generator;
 mod layout;
+mod limiter;
 mod rendering;
 mod resources;
 mod types;
@@ -18,10 +19,15 @@ use axum::{
 };
 pub use cache::OgImageCache;
 pub use generator::OgImageGenerator;
+pub use limiter::OgRenderLimiter;
 use rari_error::RariError;
 pub use types::{OgImageEntry, OgImageParams, OgImageResult};
 
-use crate::server::{ServerState, config::Config, error_response};
+use crate::server::{
+    ServerState,
+    config::{Config, OgImageConfig},
+    error_response,
+};
 
 pub async fn og_image_handler(
     State(state): State<ServerState>,
@@ -94,6 +100,53 @@ pub enum OgImageError {
     InvalidParams(String),
     #[error("Internal error: {0}")]
     InternalError(String),
+    #[error("OG renderer busy: {0}")]
+    Busy(String),
+}
+
+/// Decode a base64 `data:` image payload, refusing it before allocation when the
+/// decoded size would exceed `og_image.max_data_uri_bytes`.
+fn decode_data_uri_payload(data: &str) -> Result<Vec<u8>, RariError> {
+    let max_bytes =
+        Config::get().map_or(MAX_OG_IMAGE_BYTES, |config| config.og_image.max_data_uri_bytes);
+    decode_data_uri_payload_with_limit(data, max_bytes)
+}
+
+fn decode_data_uri_payload_with_limit(data: &str, max_bytes: usize) -> Result<Vec<u8>, RariError> {
+    use base64::{Engine as _, engine::general_purpose};
+
+    let decoded_len = data.len() / 4 * 3;
+    if decoded_len > max_bytes {
+        return Err(RariError::validation(format!(
+            "Data URI image too large ({decoded_len} bytes, max {max_bytes})"
+        )));
+    }
+
+    general_purpose::STANDARD
+        .decode(data)
+        .map_err(|e| RariError::validation(format!("Failed to decode base64: {e}")))
+}
+
+/// Apply the configured default canvas size and reject dimensions outside the
+/// configured bounds, before any layout or rasterization is attempted.
+fn resolve_og_dimensions(
+    limits: &OgImageConfig,
+    width: Option<u32>,
+    height: Option<u32>,
+) -> Result<(u32, u32), OgImageError> {
+    let width = width.unwrap_or(limits.default_width);
+    let height = height.unwrap_or(limits.default_height);
+
+    if !(limits.min_width..=limits.max_width).contains(&width)
+        || !(limits.min_height..=limits.max_height).contains(&height)
+    {
+        return Err(OgImageError::InvalidParams(format!(
+            "OG image size {width}x{height} is outside the allowed range {}x{} to {}x{}",
+            limits.min_width, limits.min_height, limits.max_width, limits.max_height
+        )));
+    }
+
+    Ok((width, height))
 }
 
 impl From<RariError> for OgImageError {
@@ -108,9 +161,9 @@ impl From<&OgImageError> for RariError {
             OgImageError::ComponentNotFound(_) => Self::not_found(err.to_string()),
             OgImageError::InvalidParams(_) => Self::validation(err.to_string()),
             OgImageError::ExecutionError(_) => Self::js_execution(err.to_string()),
-            OgImageError::GenerationError(_) | OgImageError::InternalError(_) => {
-                Self::internal(err.to_string())
-            }
+            OgImageError::GenerationError(_)
+            | OgImageError::InternalError(_)
+            | OgImageError::Busy(_) => Self::internal(err.to_string()),
         }
     }
 }
@@ -118,6 +171,48 @@ impl From<&OgImageError> for RariError {
 impl IntoResponse for OgImageError {
     fn into_response(self) -> Response {
         let is_dev = Config::get().is_some_and(Config::is_development);
-        error_response::json_response(&RariError::from(&self), is_dev)
+        let mut response = error_response::json_response(&RariError::from(&self), is_dev);
+        if matches!(self, Self::Busy(_)) {
+            *response.status_mut() = StatusCode::SERVICE_UNAVAILABLE;
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, header::HeaderValue::from_static("1"));
+        }
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_data_uri_payload_within_limit() {
+        assert_eq!(
+            decode_data_uri_payload_with_limit("aGVsbG8=", 16).ok(),
+            Some(b"hello".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_decode_data_uri_payload_rejects_oversized() {
+        let payload = "A".repeat(4 * 1024);
+        let err = decode_data_uri_payload_with_limit(&payload, 1024).err();
+        assert!(err.is_some_and(|e| e.to_string().contains("too large")));
+    }
+
+    #[test]
+    fn test_resolve_og_dimensions_applies_defaults() {
+        let limits = OgImageConfig::default();
+        assert_eq!(resolve_og_dimensions(&limits, None, None).ok(), Some((1200, 630)));
+        assert_eq!(resolve_og_dimensions(&limits, Some(1600), None).ok(), Some((1600, 630)));
+    }
+
+    #[test]
+    fn test_resolve_og_dimensions_rejects_oversized() {
+        let limits = OgImageConfig::default();
+        let err = resolve_og_dimensions(&limits, Some(20_000), Some(20_000)).err();
+        assert!(matches!(err, Some(OgImageError::InvalidParams(_))));
+        assert!(resolve_og_dimensions(&limits, Some(0), Some(630)).is_err());
     }
 }
@@ -20,7 +20,9 @@ impl ImageRenderer {
             .and_then(|v| v.as_str())
             .ok_or_else(|| RariError::validation("Image element missing src attribute"))?;
 
-        let source_image = Self::load_image(src)?;
+        let Some(source_image) = Self::load_source_image(src)? else {
+            return Ok(());
+        };
 
         let object_fit = layout.style.get("objectFit").map(String::as_str).unwrap_or("fill");
 
@@ -242,6 +244,20 @@ impl ImageRenderer {
         }
     }
 
+    /// Like [`Self::load_image`], but a `data:` image that fails to load (for
+    /// example one over the size cap) is skipped with a warning, matching layout,
+    /// which sizes it as zero.
+    fn load_source_image(src: &str) -> Result<Option<RgbaImage>, RariError> {
+        match Self::load_image(src) {
+            Ok(image) => Ok(Some(image)),
+            Err(e) if src.starts_with("data:") => {
+                tracing::warn!("Skipping OG data URI image: {e}");
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     fn load_image(src: &str) -> Result<RgbaImage, RariError> {
         if src.starts_with("http://") || src.starts_with("https://") {
             Self::load_remote_image(src)
@@ -299,10 +315,7 @@ impl ImageRenderer {
         let data = parts[1];
 
         if header.contains("base64") {
-            use base64::{Engine as _, engine::general_purpose};
-            let decoded = general_purpose::STANDARD
-                .decode(data)
-                .map_err(|e| RariError::validation(format!("Failed to decode base64: {e}")))?;
+            let decoded = super::super::decode_data_uri_payload(data)?;
 
             Ok(image::load_from_memory(&decoded)
                 .map_err(|e| RariError::internal(format!("Failed to decode image: {e}")))?
@@ -312,3 +325,17 @@ impl ImageRenderer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oversized_data_uri_image_is_skipped() {
+        let payload = "A".repeat(super::super::super::MAX_OG_IMAGE_BYTES / 3 * 4 + 4);
+        let src = format!("data:image/png;base64,{payload}");
+
+        assert!(matches!(ImageRenderer::load_source_image(&src), Ok(None)));
+        assert!(ImageRenderer::load_image(&src).is_err());
+    }
+}
@@ -5,7 +5,11 @@ use rustc_hash::FxHashMap;
 use zeno::Scratch;
 
 use super::{
-    super::{layout::ComputedLayout, resources::fonts::FontContext, types::JsxChild},
+    super::{
+        layout::{ComputedLayout, style::parse_color},
+        resources::fonts::FontContext,
+        types::JsxChild,
+    },
     mask::MaskMemory,
 };
 use crate::utils::cast;
@@ -108,36 +112,6 @@ impl ImageRenderer {
     }
 
     pub(super) fn parse_color(color_str: &str) -> Rgba<u8> {
-        match color_str {
-            "black" => Rgba([0, 0, 0, 255]),
-            "white" => Rgba([255, 255, 255, 255]),
-            "red" => Rgba([255, 0, 0, 255]),
-            "green" => Rgba([0, 255, 0, 255]),
-            "blue" => Rgba([0, 0, 255, 255]),
-            _ if color_str.starts_with('#') => {
-                let hex = color_str.trim_start_matches('#');
-                if hex.len() == 6 {
-                    let r = u8::from_str_radix(&hex[0..2], 16).unwrap_or(0);
-                    let g = u8::from_str_radix(&hex[2..4], 16).unwrap_or(0);
-                    let b = u8::from_str_radix(&hex[4..6], 16).unwrap_or(0);
-                    Rgba([r, g, b, 255])
-                } else {
-                    Rgba([0, 0, 0, 255])
-                }
-            }
-            _ if color_str.starts_with("rgb(") => {
-                let inner = color_str.trim_start_matches("rgb(").trim_end_matches(')');
-                let parts: Vec<&str> = inner.split(',').map(str::trim).collect();
-                if parts.len() == 3 {
-                    let r = parts[0].parse().unwrap_or(0);
-                    let g = parts[1].parse().unwrap_or(0);
-                    let b = parts[2].parse().unwrap_or(0);
-                    Rgba([r, g, b, 255])
-                } else {
-                    Rgba([0, 0, 0, 255])
-                }
-            }
-            _ => Rgba([0, 0, 0, 255]),
-        }
+        parse_color(color_str).unwrap_or(Rgba([0, 0, 0, 255]))
     }
 }
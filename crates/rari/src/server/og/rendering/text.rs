@@ -14,7 +14,7 @@ use swash::{
 use zeno::{Mask, PathData};
 
 use super::{
-    super::{layout::ComputedLayout, types::JsxChild},
+    super::layout::{ComputedLayout, element_text},
     renderer::ImageRenderer,
 };
 use crate::utils::{cast, float};
@@ -36,6 +36,8 @@ pub(super) struct GlyphRenderParams {
     pub line_height: f32,
     pub text_align: Alignment,
     pub text_decoration: Vec<TextDecoration>,
+    /// Paint every glyph as a flat outline in `color`, as for a shadow pass.
+    pub monochrome: bool,
 }
 
 impl ImageRenderer {
@@ -44,7 +46,7 @@ impl ImageRenderer {
         layout: &ComputedLayout,
         image: &mut RgbaImage,
     ) -> Result<(), RariError> {
-        let text = Self::extract_text(&layout.element);
+        let text = element_text(&layout.element, layout.white_space);
         if text.is_empty() {
             return Ok(());
         }
@@ -52,8 +54,7 @@ impl ImageRenderer {
         let font_size =
             layout.style.get("fontSize").and_then(|s| s.parse::<f32>().ok()).unwrap_or(16.0);
 
-        let color =
-            layout.style.get("color").map(|c| Self::parse_color(c)).unwrap_or(Rgba([0, 0, 0, 255]));
+        let color = layout.color.unwrap_or(Rgba([0, 0, 0, 255]));
 
         let font_weight = Self::parse_font_weight(&layout.style);
 
@@ -69,35 +70,38 @@ impl ImageRenderer {
             font_size,
             font_weight,
             color,
-            max_width: Some(
+            max_width: layout.white_space.wrap_width(Some(
                 layout.width
                     - layout.border.left
                     - layout.border.right
                     - layout.padding.left
                     - layout.padding.right,
-            ),
+            )),
             line_height,
             text_align,
             text_decoration,
+            monochrome: false,
         };
 
+        // Blur is parsed but not painted; the offset copy alone gives
+        // legibility over busy backgrounds.
+        if let Some(shadow) = layout.text_shadow {
+            let shadow_params = GlyphRenderParams {
+                x: params.x + shadow.offset_x,
+                y: params.y + shadow.offset_y,
+                color: shadow.color.unwrap_or(color),
+                text_decoration: Vec::new(),
+                monochrome: true,
+                ..params
+            };
+            self.render_glyphs(&text, &shadow_params, image)?;
+        }
+
         self.render_glyphs(&text, &params, image)?;
 
         Ok(())
     }
 
-    fn extract_text(element: &super::super::types::JsxElement) -> String {
-        element
-            .children
-            .iter()
-            .filter_map(|child| match child {
-                JsxChild::Text(text) => Some(text.as_str()),
-                JsxChild::Element(_) => None,
-            })
-            .collect::<Vec<_>>()
-            .join("")
-    }
-
     fn parse_line_height(style: &rustc_hash::FxHashMap<String, String>, font_size: f32) -> f32 {
         if let Some(lh) = style.get("lineHeight") {
             if let Ok(multiplier) = lh.parse::<f32>() {
@@ -241,7 +245,11 @@ impl ImageRenderer {
                     let glyph_x = params.x + glyph.x;
                     let glyph_y = params.y + glyph.y;
 
-                    if let Some(bitmap) =
+                    if params.monochrome {
+                        if let Some(outline) = scaler.scale_outline(cast::u32_to_u16(glyph.id)) {
+                            self.draw_outline(&outline, glyph_x, glyph_y, params.color, image)?;
+                        }
+                    } else if let Some(bitmap) =
                         scaler.scale_color_bitmap(cast::u32_to_u16(glyph.id), StrikeWith::BestFit)
                     {
                         self.draw_color_bitmap(&bitmap, glyph_x, glyph_y, image);
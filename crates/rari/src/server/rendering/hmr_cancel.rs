@@ -0,0 +1,154 @@
+use std::sync::{Arc, Weak};
+
+use parking_lot::Mutex;
+use tokio::sync::watch;
+
+/// Cancels in-flight development renders when a hot-reloaded module changes.
+///
+/// Each render takes a [`RenderCancelToken`] tagged with the component ids of
+/// its route (layouts and page). A module change signals only the tokens whose
+/// route uses that module, so each closes its stream and the client re-requests
+/// against the fresh module; renders of unrelated routes keep streaming.
+/// Disabled outside development.
+pub struct HmrRenderCancellation {
+    enabled: bool,
+    renders: Mutex<Vec<Weak<ActiveRender>>>,
+}
+
+struct ActiveRender {
+    component_ids: Vec<String>,
+    cancelled: watch::Sender<bool>,
+}
+
+/// Resolves once a module used by this render's route changes.
+pub struct RenderCancelToken {
+    render: Option<Arc<ActiveRender>>,
+}
+
+impl HmrRenderCancellation {
+    #[must_use]
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled, renders: Mutex::new(Vec::new()) }
+    }
+
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Number of renders currently holding a token.
+    #[must_use]
+    pub fn active(&self) -> usize {
+        self.renders.lock().iter().filter(|render| render.strong_count() > 0).count()
+    }
+
+    #[must_use]
+    pub fn token(&self, component_ids: Vec<String>) -> RenderCancelToken {
+        if !self.enabled {
+            return RenderCancelToken { render: None };
+        }
+
+        let render = Arc::new(ActiveRender { component_ids, cancelled: watch::Sender::new(false) });
+        let mut renders = self.renders.lock();
+        renders.retain(|render| render.strong_count() > 0);
+        renders.push(Arc::downgrade(&render));
+        RenderCancelToken { render: Some(render) }
+    }
+
+    /// Cancel every active render whose route uses `component_id`. Returns how
+    /// many renders were signalled.
+    pub fn cancel_active(&self, component_id: &str) -> usize {
+        if !self.enabled {
+            return 0;
+        }
+
+        let mut renders = self.renders.lock();
+        renders.retain(|render| render.strong_count() > 0);
+        let mut signalled = 0;
+        for render in renders.iter().filter_map(Weak::upgrade) {
+            if render.component_ids.iter().any(|id| id == component_id) {
+                render.cancelled.send_replace(true);
+                signalled += 1;
+            }
+        }
+        signalled
+    }
+}
+
+impl RenderCancelToken {
+    pub async fn cancelled(&mut self) {
+        let Some(render) = self.render.as_ref() else {
+            return std::future::pending().await;
+        };
+
+        let mut rx = render.cancelled.subscribe();
+        if rx.wait_for(|cancelled| *cancelled).await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn route_ids() -> Vec<String> {
+        vec!["app/layout_aaaaaaaa".to_string(), "app/blog/page_bbbbbbbb".to_string()]
+    }
+
+    #[tokio::test]
+    async fn test_module_change_cancels_active_render() {
+        let cancellation = HmrRenderCancellation::new(true);
+        let mut token = cancellation.token(route_ids());
+        assert_eq!(cancellation.active(), 1);
+
+        assert_eq!(cancellation.cancel_active("app/layout_aaaaaaaa"), 1);
+        let result = tokio::time::timeout(Duration::from_millis(100), token.cancelled()).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_unrelated_module_change_leaves_render_running() {
+        let cancellation = HmrRenderCancellation::new(true);
+        let mut token = cancellation.token(route_ids());
+        let mut other = cancellation.token(vec!["app/about/page_cccccccc".to_string()]);
+
+        assert_eq!(cancellation.cancel_active("app/about/page_cccccccc"), 1);
+        let result = tokio::time::timeout(Duration::from_millis(100), other.cancelled()).await;
+        assert!(result.is_ok());
+        let result = tokio::time::timeout(Duration::from_millis(20), token.cancelled()).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_render_started_after_change_is_not_cancelled() {
+        let cancellation = HmrRenderCancellation::new(true);
+        cancellation.cancel_active("app/layout_aaaaaaaa");
+
+        let mut token = cancellation.token(route_ids());
+        let result = tokio::time::timeout(Duration::from_millis(20), token.cancelled()).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_finished_render_is_not_counted() {
+        let cancellation = HmrRenderCancellation::new(true);
+        let token = cancellation.token(route_ids());
+        drop(token);
+
+        assert_eq!(cancellation.active(), 0);
+        assert_eq!(cancellation.cancel_active("app/layout_aaaaaaaa"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_disabled_cancellation_never_fires() {
+        let cancellation = HmrRenderCancellation::new(false);
+        let mut token = cancellation.token(route_ids());
+        assert_eq!(cancellation.cancel_active("app/layout_aaaaaaaa"), 0);
+
+        let result = tokio::time::timeout(Duration::from_millis(20), token.cancelled()).await;
+        assert!(result.is_err());
+    }
+}
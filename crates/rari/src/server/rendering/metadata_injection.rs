@@ -683,6 +683,7 @@ mod tests {
             template_navigation_id: None,
             metadata: None,
             streaming_head_extra: None,
+            client_ip: None,
         };
         apply_blocking_streaming_metadata(&mut context, Some(metadata), None);
         let extra = context.streaming_head_extra.as_deref().expect("bot head tags");
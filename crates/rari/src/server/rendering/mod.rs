@@ -1,6 +1,8 @@
+pub mod hmr_cancel;
 pub mod html_bots;
 pub mod metadata;
 pub mod metadata_injection;
 pub mod pretty_html;
+pub mod render_queue;
 pub mod streaming_response;
 pub mod utils;
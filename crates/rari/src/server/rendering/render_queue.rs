@@ -0,0 +1,187 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use parking_lot::Mutex;
+use tokio::sync::oneshot;
+
+use crate::server::config::RenderPriority;
+
+/// Bounded admission for page renders.
+///
+/// At most `max_concurrent` renders hold a [`RenderPermit`] at once. When a
+/// permit is released the slot is handed to the oldest high-priority waiter
+/// first, so interactive routes never wait behind queued batch renders. A
+/// `max_concurrent` of `0` disables the queue and every permit is free.
+pub struct RenderScheduler {
+    max_concurrent: usize,
+    state: Mutex<SchedulerState>,
+}
+
+#[derive(Default)]
+struct SchedulerState {
+    active: usize,
+    high: VecDeque<oneshot::Sender<RenderPermit>>,
+    low: VecDeque<oneshot::Sender<RenderPermit>>,
+}
+
+/// Holds one render slot until dropped.
+#[must_use]
+pub struct RenderPermit {
+    scheduler: Option<Arc<RenderScheduler>>,
+}
+
+impl RenderScheduler {
+    #[must_use]
+    pub fn new(max_concurrent: usize) -> Self {
+        Self { max_concurrent, state: Mutex::new(SchedulerState::default()) }
+    }
+
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.max_concurrent > 0
+    }
+
+    #[must_use]
+    pub fn active(&self) -> usize {
+        self.state.lock().active
+    }
+
+    #[must_use]
+    pub fn queued(&self) -> usize {
+        let state = self.state.lock();
+        state.high.len() + state.low.len()
+    }
+
+    pub async fn acquire(self: &Arc<Self>, priority: RenderPriority) -> RenderPermit {
+        if !self.is_enabled() {
+            return RenderPermit { scheduler: None };
+        }
+
+        let rx = {
+            let mut state = self.state.lock();
+            if state.active < self.max_concurrent {
+                state.active += 1;
+                return RenderPermit { scheduler: Some(Arc::clone(self)) };
+            }
+
+            let (tx, rx) = oneshot::channel();
+            match priority {
+                RenderPriority::High => state.high.push_back(tx),
+                RenderPriority::Low => state.low.push_back(tx),
+            }
+            rx
+        };
+
+        // The sender is only dropped unsent if the scheduler itself is torn
+        // down; fall back to an unbounded permit rather than failing the render.
+        rx.await.unwrap_or(RenderPermit { scheduler: None })
+    }
+
+    /// Pass the released slot to the next live waiter, or free it.
+    fn release(self: Arc<Self>) {
+        loop {
+            let next = {
+                let mut state = self.state.lock();
+                match state.high.pop_front().or_else(|| state.low.pop_front()) {
+                    Some(tx) => tx,
+                    None => {
+                        state.active = state.active.saturating_sub(1);
+                        return;
+                    }
+                }
+            };
+
+            match next.send(RenderPermit { scheduler: Some(Arc::clone(&self)) }) {
+                Ok(()) => return,
+                // The waiter was cancelled; disarm the returned permit so it
+                // does not release recursively and try the next waiter.
+                Err(mut permit) => {
+                    permit.scheduler = None;
+                }
+            }
+        }
+    }
+}
+
+impl Drop for RenderPermit {
+    fn drop(&mut self) {
+        if let Some(scheduler) = self.scheduler.take() {
+            scheduler.release();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn wait_for_queued(scheduler: &RenderScheduler, count: usize) {
+        while scheduler.queued() < count {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_disabled_scheduler_does_not_limit() {
+        let scheduler = Arc::new(RenderScheduler::new(0));
+        let _a = scheduler.acquire(RenderPriority::Low).await;
+        let _b = scheduler.acquire(RenderPriority::Low).await;
+        assert_eq!(scheduler.active(), 0);
+        assert_eq!(scheduler.queued(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_high_priority_render_runs_ahead_of_queued_low_priority() {
+        let scheduler = Arc::new(RenderScheduler::new(1));
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        let running = scheduler.acquire(RenderPriority::Low).await;
+
+        let mut handles = Vec::new();
+        for (label, priority) in [
+            ("low-1", RenderPriority::Low),
+            ("low-2", RenderPriority::Low),
+            ("high", RenderPriority::High),
+        ] {
+            let queued = scheduler.queued();
+            let task_scheduler = Arc::clone(&scheduler);
+            let order = Arc::clone(&order);
+            handles.push(tokio::spawn(async move {
+                let _permit = task_scheduler.acquire(priority).await;
+                order.lock().push(label);
+            }));
+            wait_for_queued(&scheduler, queued + 1).await;
+        }
+
+        assert_eq!(scheduler.active(), 1);
+        drop(running);
+
+        for handle in handles {
+            let _ = handle.await;
+        }
+
+        assert_eq!(*order.lock(), ["high", "low-1", "low-2"]);
+        assert_eq!(scheduler.active(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_waiter_does_not_leak_slot() {
+        let scheduler = Arc::new(RenderScheduler::new(1));
+        let running = scheduler.acquire(RenderPriority::High).await;
+
+        let cancelled = tokio::spawn({
+            let scheduler = Arc::clone(&scheduler);
+            async move {
+                let _permit = scheduler.acquire(RenderPriority::High).await;
+            }
+        });
+        wait_for_queued(&scheduler, 1).await;
+        cancelled.abort();
+        let _ = cancelled.await;
+
+        drop(running);
+        assert_eq!(scheduler.active(), 0);
+
+        let _next = scheduler.acquire(RenderPriority::Low).await;
+        assert_eq!(scheduler.active(), 1);
+    }
+}
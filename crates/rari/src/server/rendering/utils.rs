@@ -157,7 +157,8 @@ pub async fn inject_assets_into_html(html: &str, config: &Config) -> Result<Stri
         inject_assets_into_complete_document(html, config).await
     } else {
         inject_content_into_template(html, config).await
-    };
+    }
+    .map(|final_html| apply_base_path(&final_html, &config.server.base_path));
 
     match &result {
         Ok(final_html) => {
@@ -201,7 +202,8 @@ async fn inject_assets_into_complete_document(
 ) -> Result<String, StatusCode> {
     let has_root_before = html.contains(r#"id="root""#);
 
-    let template_path = if config.is_development() { "index.html" } else { "dist/index.html" };
+    let template_path =
+        if config.uses_source_template() { "index.html" } else { "dist/index.html" };
 
     let Ok(template) = fs::read_to_string(template_path).await else {
         let trimmed_lower = html.trim_start().cow_to_lowercase();
@@ -407,7 +409,8 @@ async fn inject_content_into_template(
     content: &str,
     config: &Config,
 ) -> Result<String, StatusCode> {
-    let template_path = if config.is_development() { "index.html" } else { "dist/index.html" };
+    let template_path =
+        if config.uses_source_template() { "index.html" } else { "dist/index.html" };
 
     let Ok(template) = fs::read_to_string(template_path).await else {
         return Ok(format!(
@@ -544,3 +547,135 @@ import 'http://localhost:{vite_port}/@id/virtual:rari-entry-client';
 {html}"#
     )
 }
+
+/// Prefixes root-relative `/assets/` URLs for apps served under a sub-path.
+/// Only `src`/`href` on `<script>` and `<link>` tags, the injected asset tags,
+/// are rewritten; page content is left alone. No `<base href>` is emitted, as it
+/// would also re-resolve fragment and relative links in the page. A base of `/`
+/// leaves the HTML untouched.
+pub fn apply_base_path(html: &str, base_path: &str) -> String {
+    let trimmed = base_path.trim_matches('/');
+    if trimmed.is_empty() {
+        return html.to_string();
+    }
+
+    prefix_asset_tag_urls(html, &format!("/{trimmed}/"))
+}
+
+/// Length of the prefix of a streamed HTML buffer that [`apply_base_path`] can
+/// rewrite now. A trailing `<script`/`<link` tag cut off mid-way, or a split
+/// UTF-8 sequence, is left for the next chunk to complete.
+pub fn base_path_rewrite_boundary(buf: &[u8]) -> usize {
+    let valid = match std::str::from_utf8(buf) {
+        Ok(_) => buf.len(),
+        Err(e) if e.error_len().is_none() => e.valid_up_to(),
+        Err(_) => return buf.len(),
+    };
+
+    let Some(open) = buf[..valid].iter().rposition(|&b| b == b'<') else {
+        return valid;
+    };
+    let tail = &buf[open..valid];
+    let is_open_asset_tag = !tail.contains(&b'>')
+        && [b"<script".as_slice(), b"<link".as_slice()]
+            .iter()
+            .any(|tag| tag.starts_with(tail) || tail.starts_with(tag));
+
+    if is_open_asset_tag { open } else { valid }
+}
+
+fn prefix_asset_tag_urls(html: &str, base: &str) -> String {
+    let src = format!(r#"src="{base}assets/"#);
+    let href = format!(r#"href="{base}assets/"#);
+
+    let mut result = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(tag_start) =
+        [find_open_tag(rest, "<script"), find_open_tag(rest, "<link")].into_iter().flatten().min()
+    {
+        let tag_end = rest[tag_start..].find('>').map_or(rest.len(), |end| tag_start + end + 1);
+        result.push_str(&rest[..tag_start]);
+        result.push_str(
+            &rest[tag_start..tag_end]
+                .cow_replace(r#"src="/assets/"#, &src)
+                .cow_replace(r#"href="/assets/"#, &href),
+        );
+        rest = &rest[tag_end..];
+    }
+    result.push_str(rest);
+
+    result
+}
+
+/// Offset of an open tag such as `<head`, skipping look-alikes such as `<header>`.
+fn find_open_tag(html: &str, tag: &str) -> Option<usize> {
+    html.match_indices(tag).map(|(idx, _)| idx).find(|&idx| {
+        html[idx + tag.len()..]
+            .chars()
+            .next()
+            .is_some_and(|c| c == '>' || c == '/' || c.is_ascii_whitespace())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_base_path_rewrites_assets() {
+        let html = r#"<html><head><link rel="stylesheet" href="/assets/app.css"></head><body><script type="module" src="/assets/app.js"></script></body></html>"#;
+
+        let result = apply_base_path(html, "/app/");
+
+        assert!(!result.contains("<base "));
+        assert!(result.contains(r#"href="/app/assets/app.css""#));
+        assert!(result.contains(r#"src="/app/assets/app.js""#));
+    }
+
+    #[test]
+    fn test_apply_base_path_leaves_page_content_alone() {
+        let html = r#"<html><head><script type="module" src="/assets/app.js"></script></head><body><a href="/assets/report.pdf">Report</a><pre>src="/assets/x.js"</pre></body></html>"#;
+
+        let result = apply_base_path(html, "/app/");
+
+        assert!(result.contains(r#"src="/app/assets/app.js""#));
+        assert!(result.contains(r#"<a href="/assets/report.pdf">"#));
+        assert!(result.contains(r#"<pre>src="/assets/x.js"</pre>"#));
+    }
+
+    #[test]
+    fn test_apply_base_path_root_is_noop() {
+        let html =
+            r#"<html><head></head><body><script src="/assets/app.js"></script></body></html>"#;
+
+        assert_eq!(apply_base_path(html, "/"), html);
+        assert_eq!(apply_base_path(html, ""), html);
+    }
+
+    #[test]
+    fn test_apply_base_path_keeps_fragment_links_on_the_page() {
+        let html = r##"<html><head><script src="/assets/app.js"></script></head><body><a href="#section">Jump</a><a href="guide">Guide</a></body></html>"##;
+
+        let result = apply_base_path(html, "/app/");
+
+        assert!(!result.contains("<base "));
+        assert!(result.contains(r##"<a href="#section">"##));
+        assert!(result.contains(r#"<a href="guide">"#));
+    }
+
+    #[test]
+    fn test_apply_base_path_skips_header_element() {
+        let html = r#"<html><body><header class="top"><a href="/assets/x.js">x</a></header></body></html>"#;
+        assert_eq!(apply_base_path(html, "/app/"), html);
+    }
+
+    #[test]
+    fn test_base_path_rewrite_boundary_holds_back_cut_off_asset_tags() {
+        assert_eq!(base_path_rewrite_boundary(b"<div>done</div>"), 15);
+        assert_eq!(base_path_rewrite_boundary(b"<div></div><script src=\"/ass"), 11);
+        assert_eq!(base_path_rewrite_boundary(b"<p>a</p><li"), 8);
+        assert_eq!(base_path_rewrite_boundary(b"<p>x</p><em"), 11);
+        assert_eq!(base_path_rewrite_boundary(b"if(a<b){"), 8);
+        assert_eq!(base_path_rewrite_boundary(&"<p>\u{e9}".as_bytes()[..4]), 3);
+    }
+}
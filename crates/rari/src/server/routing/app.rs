@@ -14,11 +14,15 @@ use axum::{
     body,
     body::Body,
     extract::{Query, State},
-    http::{HeaderMap, HeaderValue, StatusCode, Uri, header::CACHE_CONTROL},
+    http::{
+        Extensions, HeaderMap, HeaderValue, Method, StatusCode, Uri,
+        header::{CACHE_CONTROL, LINK},
+    },
     response::Response,
 };
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use cow_utils::CowUtils;
+use futures::{Stream, StreamExt};
 use rari_error::RariError;
 use rustc_hash::FxHashMap;
 use tokio::{
@@ -31,16 +35,18 @@ use tokio::{
 };
 
 use crate::{
+    RscHtmlRenderer,
     rendering::layout::{
         ChunkedContentType, LayoutRenderContext, LayoutRenderer, OpenGraphImage,
         OpenGraphImageDescriptor, OpenGraphMetadata, PageMetadata, RenderResult, TwitterMetadata,
-        component_dist_path, create_layout_context, drain_chunked_stream, sort_flight_protocol,
+        component_dist_path, create_component_id, create_layout_context, drain_chunked_stream,
+        sort_flight_protocol,
     },
     server::{
         ServerState,
         actions::{
             has_action_form_state_cookie, parse_action_form_state_from_cookie,
-            response_cache_cookie_partition,
+            response_cache_cookie_partition, validate_redirect_url,
         },
         cache::response,
         compression::{CompressionEncoding, compress_body, compress_stream},
@@ -51,22 +57,27 @@ use crate::{
                 self,
                 http::{
                     extract_headers, extract_search_params, get_content_type,
-                    merge_vary_with_accept,
+                    merge_vary_with_accept, resolve_request_origin,
                 },
-                path_validation::validate_safe_path,
             },
         },
         error_response,
         middleware::request_context::RequestContext,
         rendering::{
+            hmr_cancel::RenderCancelToken,
             html_bots::is_html_limited_bot,
             metadata_injection::{
                 apply_blocking_streaming_metadata, inject_metadata, streaming_metadata_chunk,
             },
             pretty_html::pretty_print_html,
-            utils::{inject_assets_into_html, inject_vite_client},
+            render_queue::RenderPermit,
+            utils::{
+                apply_base_path, base_path_rewrite_boundary, inject_assets_into_html,
+                inject_vite_client,
+            },
         },
         routing::app_router::AppRouteMatch,
+        static_assets::resolve_public_file,
     },
     utils::path::path_to_file_url,
 };
@@ -206,14 +217,14 @@ pub(crate) fn wrap_html_with_metadata(
         html_content
     };
 
-    if state.config.is_development() { pretty_print_html(&html) } else { html }
+    if state.config.pretty_print_html() { pretty_print_html(&html) } else { html }
 }
 
 fn should_use_streaming(route_match: &AppRouteMatch, config: &Config) -> bool {
     if route_match.not_found.is_some() {
         return false;
     }
-    config.loading.enabled && route_match.loading.is_some()
+    route_match.loading.is_some() && config.should_stream(&route_match.pathname)
 }
 
 /// Start `generateMetadata` off the critical path. Caller awaits/joins before
@@ -368,15 +379,12 @@ async fn inject_og_image_into_metadata(
 }
 
 fn get_base_url_from_context(context: &LayoutRenderContext, config: &Config) -> String {
-    if let Some(host) = context.headers.get("host") {
-        let protocol = context
-            .headers
-            .get("x-forwarded-proto")
-            .or_else(|| context.headers.get("x-forwarded-protocol"))
-            .map(String::as_str)
-            .unwrap_or_else(|| if config.is_production() { "https" } else { "http" });
-
-        format!("{protocol}://{host}")
+    let default_scheme = if config.is_production() { "https" } else { "http" };
+
+    if let Some(origin) =
+        resolve_request_origin(&context.headers, context.client_ip, &config.server, default_scheme)
+    {
+        origin.to_origin_string()
     } else if config.is_production() {
         "https://localhost".to_string()
     } else {
@@ -384,12 +392,61 @@ fn get_base_url_from_context(context: &LayoutRenderContext, config: &Config) ->
     }
 }
 
+/// Request-scoped limits a render keeps for as long as it runs. Streamed
+/// bodies carry a clone, so they stay bounded after the handler returns.
+#[derive(Clone)]
+pub struct RenderBounds {
+    permit: Arc<RenderPermit>,
+    deadline: Option<time::Instant>,
+    component_ids: Vec<String>,
+}
+
+impl RenderBounds {
+    /// The render-queue slot is released once every clone has dropped.
+    #[must_use]
+    pub fn new(permit: RenderPermit) -> Self {
+        Self { permit: Arc::new(permit), deadline: None, component_ids: Vec::new() }
+    }
+
+    /// Wall-clock instant by which the render and its stream must finish.
+    #[must_use]
+    pub fn with_deadline(mut self, deadline: Option<time::Instant>) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Component ids of the route's layouts and page; a hot reload of any of
+    /// them cancels the streamed render.
+    #[must_use]
+    pub fn with_component_ids(mut self, component_ids: Vec<String>) -> Self {
+        self.component_ids = component_ids;
+        self
+    }
+
+    /// Runs a buffered render step under the request deadline, dropping it and
+    /// failing with a timeout once the deadline passes.
+    async fn within_deadline<T, F>(&self, render: F) -> Result<T, RariError>
+    where
+        F: Future<Output = Result<T, RariError>>,
+    {
+        let Some(deadline) = self.deadline else {
+            return render.await;
+        };
+
+        time::timeout_at(deadline, render).await.unwrap_or_else(|_| {
+            tracing::error!("Render exceeded its deadline; aborting");
+            Err(RariError::timeout("Render deadline exceeded"))
+        })
+    }
+}
+
 pub async fn render_with_fallback(
     state: Arc<ServerState>,
     route_match: AppRouteMatch,
     context: LayoutRenderContext,
     accept_encoding: Option<&str>,
     metadata_rx: Option<oneshot::Receiver<Option<PageMetadata>>>,
+    bounds: RenderBounds,
 ) -> Result<Response, StatusCode> {
     let layout_renderer = LayoutRenderer::with_shared_cache(
         Arc::clone(&state.renderer),
@@ -403,13 +460,14 @@ pub async fn render_with_fallback(
         &layout_renderer,
         accept_encoding,
         metadata_rx,
+        bounds.clone(),
     )
     .await
     {
         Ok(response) => Ok(response),
         Err(e) => {
             tracing::error!("Streaming render failed, falling back to synchronous: {}", e);
-            render_synchronous(state, route_match, context, accept_encoding).await
+            render_synchronous(state, route_match, context, accept_encoding, bounds).await
         }
     }
 }
@@ -419,6 +477,7 @@ pub async fn render_rsc_navigation_streaming(
     route_match: AppRouteMatch,
     context: LayoutRenderContext,
     accept_encoding: Option<&str>,
+    bounds: RenderBounds,
 ) -> Result<Response, StatusCode> {
     let layout_renderer = LayoutRenderer::with_shared_cache(
         Arc::clone(&state.renderer),
@@ -431,18 +490,21 @@ pub async fn render_rsc_navigation_streaming(
             .with_http_headers(context.headers.clone()),
     );
 
-    let render_result = match layout_renderer
-        .render_route_with_streaming(
+    let render_result = match bounds
+        .within_deadline(layout_renderer.render_route_with_streaming(
             &route_match,
             &context,
             Some(Arc::clone(&request_context)),
             true,
             None,
-        )
+        ))
         .await
     {
         Ok(result) => result,
         Err(e) => {
+            if let Some(response) = render_redirect_response(&e, &state.config) {
+                return Ok(response);
+            }
             tracing::error!(
                 "Failed to render RSC navigation for streaming '{}': {}",
                 route_match.route.path,
@@ -468,6 +530,7 @@ pub async fn render_rsc_navigation_streaming(
             is_not_found,
             accept_encoding,
             None,
+            bounds,
         )),
         RenderResult::Chunked { content_type: ChunkedContentType::Html, .. } => {
             tracing::error!("HTML chunked render not supported in RSC-only mode");
@@ -541,9 +604,12 @@ fn render_chunked_response(
     is_not_found: bool,
     accept_encoding: Option<&str>,
     mut metadata_rx: Option<oneshot::Receiver<Option<PageMetadata>>>,
+    bounds: RenderBounds,
 ) -> http::Response<Body> {
     let stall_timeout = Duration::from_millis(chunked_stream_stall_timeout_ms());
     let image_optimizer = state.image_optimizer.clone();
+    // The byte stream below owns `closing`; an interrupted render still needs it.
+    let interrupted_closing = closing.clone();
 
     let byte_stream = async_stream::stream! {
         match content_type {
@@ -770,23 +836,45 @@ fn render_chunked_response(
         }
     };
 
+    // Streamed HTML never passes through `inject_assets_into_html`.
+    let byte_stream = rewrite_streamed_asset_urls(
+        byte_stream,
+        matches!(content_type, ChunkedContentType::Html)
+            .then(|| state.config.server.base_path.clone()),
+    );
+    let byte_stream = bound_by_hmr_cancel(
+        byte_stream,
+        state.hmr_render_cancellation.token(bounds.component_ids),
+        content_type,
+        interrupted_closing.clone(),
+    );
+    let byte_stream =
+        bound_by_render_deadline(byte_stream, bounds.deadline, content_type, interrupted_closing);
+    let byte_stream = hold_render_permit(byte_stream, bounds.permit);
+    let byte_stream = coalesce_small_chunks(byte_stream, state.config.server.stream_coalescing());
+
     let encoding = match content_type {
         // Prefer identity for streaming HTML so compressor setup does not delay the shell.
-        ChunkedContentType::Html => CompressionEncoding::Identity,
-        ChunkedContentType::RscFlight => CompressionEncoding::from_accept_encoding(accept_encoding),
+        ChunkedContentType::Html if !state.config.server.compress_streaming_html => {
+            CompressionEncoding::Identity
+        }
+        ChunkedContentType::Html | ChunkedContentType::RscFlight => {
+            CompressionEncoding::from_accept_encoding(accept_encoding)
+        }
     };
     let compressed_stream = compress_stream(byte_stream, encoding);
     let vary =
         if encoding.as_header_value().is_some() { "Accept, Accept-Encoding" } else { "Accept" };
 
     let status_code = if is_not_found { StatusCode::NOT_FOUND } else { StatusCode::OK };
-    let cache_control = state.config.get_cache_control_for_route(&context.pathname);
+    // Part of the response head, so it is sent before any body chunk is flushed.
+    let cache_control = state.config.cache_control_for_route(&context.pathname);
 
     let mut response_builder = Response::builder()
         .status(status_code)
         .header("transfer-encoding", "chunked")
         .header("x-render-mode", "streaming")
-        .header("cache-control", cache_control)
+        .header("cache-control", cache_control.as_str())
         .header("vary", vary)
         .header("x-content-type-options", "nosniff");
 
@@ -816,6 +904,252 @@ fn render_chunked_response(
     response_builder.body(body).expect("Valid chunked response")
 }
 
+/// Merges consecutive small chunks into one write of at least `min_bytes`,
+/// holding a partial buffer for at most `max_latency`. The first chunk (the
+/// shell) and whatever is buffered when the input ends or errors are flushed
+/// immediately, so time to first byte and stream completion are never delayed.
+fn coalesce_small_chunks<S>(
+    input: S,
+    coalescing: Option<(usize, Duration)>,
+) -> impl Stream<Item = Result<Bytes, Error>> + Send + 'static
+where
+    S: Stream<Item = Result<Bytes, Error>> + Send + 'static,
+{
+    async_stream::stream! {
+        let mut input = Box::pin(input);
+
+        let Some((min_bytes, max_latency)) = coalescing else {
+            while let Some(item) = input.next().await {
+                yield item;
+            }
+            return;
+        };
+
+        match input.next().await {
+            Some(item) => yield item,
+            None => return,
+        }
+
+        let mut buf = BytesMut::new();
+        loop {
+            match input.next().await {
+                Some(Ok(chunk)) => buf.extend_from_slice(&chunk),
+                Some(Err(e)) => {
+                    yield Err(e);
+                    return;
+                }
+                None => return,
+            }
+
+            let flush_at = time::Instant::now() + max_latency;
+            while buf.len() < min_bytes {
+                match time::timeout_at(flush_at, input.next()).await {
+                    Ok(Some(Ok(chunk))) => buf.extend_from_slice(&chunk),
+                    Ok(Some(Err(e))) => {
+                        yield Ok(buf.split().freeze());
+                        yield Err(e);
+                        return;
+                    }
+                    Ok(None) => {
+                        yield Ok(buf.split().freeze());
+                        return;
+                    }
+                    Err(_) => break,
+                }
+            }
+
+            yield Ok(buf.split().freeze());
+        }
+    }
+}
+
+/// Caps the total wall-clock time of a chunked render at the request's deadline,
+/// which already counts the shell render. Chunks already received are flushed as
+/// they arrive; once the deadline passes the remaining content is replaced by an
+/// error and the stream ends, dropping the receiver so the isolate stops writing.
+fn bound_by_render_deadline<S>(
+    input: S,
+    deadline: Option<time::Instant>,
+    content_type: ChunkedContentType,
+    closing: Bytes,
+) -> impl Stream<Item = Result<Bytes, Error>> + Send + 'static
+where
+    S: Stream<Item = Result<Bytes, Error>> + Send + 'static,
+{
+    let expired = deadline.map(|deadline| async move {
+        time::sleep_until(deadline).await;
+        tracing::error!("Render exceeded its deadline; closing stream");
+    });
+    end_stream_on(
+        input,
+        expired,
+        content_type,
+        closing,
+        "Render deadline exceeded",
+        "RSC render deadline exceeded",
+    )
+}
+
+/// Keeps the render-queue slot until the body ends or is dropped, so a streamed
+/// render counts against the queue for its whole lifetime, not just its shell.
+fn hold_render_permit<S>(
+    input: S,
+    permit: Arc<RenderPermit>,
+) -> impl Stream<Item = Result<Bytes, Error>> + Send + 'static
+where
+    S: Stream<Item = Result<Bytes, Error>> + Send + 'static,
+{
+    async_stream::stream! {
+        let _permit = permit;
+        let mut input = Box::pin(input);
+        while let Some(item) = input.next().await {
+            yield item;
+        }
+    }
+}
+
+/// Applies [`apply_base_path`] to every chunk of a streamed HTML body, not just
+/// the shell. An asset tag cut off at a chunk boundary is held back and
+/// rewritten with the next chunk. `None` passes `input` through untouched.
+fn rewrite_streamed_asset_urls<S>(
+    input: S,
+    base_path: Option<String>,
+) -> impl Stream<Item = Result<Bytes, Error>> + Send + 'static
+where
+    S: Stream<Item = Result<Bytes, Error>> + Send + 'static,
+{
+    fn rewrite(chunk: Vec<u8>, base_path: &str) -> Bytes {
+        match String::from_utf8(chunk) {
+            Ok(html) => Bytes::from(apply_base_path(&html, base_path)),
+            Err(e) => Bytes::from(e.into_bytes()),
+        }
+    }
+
+    let base_path = base_path.filter(|base_path| !base_path.trim_matches('/').is_empty());
+
+    async_stream::stream! {
+        let mut input = Box::pin(input);
+        let Some(base_path) = base_path else {
+            while let Some(item) = input.next().await {
+                yield item;
+            }
+            return;
+        };
+
+        let mut pending = Vec::new();
+        while let Some(item) = input.next().await {
+            match item {
+                Ok(chunk) => {
+                    pending.extend_from_slice(&chunk);
+                    let held = pending.split_off(base_path_rewrite_boundary(&pending));
+                    let ready = std::mem::replace(&mut pending, held);
+                    if !ready.is_empty() {
+                        yield Ok(rewrite(ready, &base_path));
+                    }
+                }
+                Err(e) => {
+                    if !pending.is_empty() {
+                        yield Ok(rewrite(std::mem::take(&mut pending), &base_path));
+                    }
+                    yield Err(e);
+                }
+            }
+        }
+        if !pending.is_empty() {
+            yield Ok(rewrite(pending, &base_path));
+        }
+    }
+}
+
+fn route_component_ids(route_match: &AppRouteMatch) -> Vec<String> {
+    route_match
+        .layouts
+        .iter()
+        .map(|layout| (&layout.component_id, &layout.file_path))
+        .chain(std::iter::once((&route_match.route.component_id, &route_match.route.file_path)))
+        .map(|(component_id, file_path)| {
+            component_id.clone().unwrap_or_else(|| create_component_id(file_path))
+        })
+        .collect()
+}
+
+/// Ends a dev render when a hot module change lands mid-stream. The client sees a
+/// closed stream and re-requests, picking up the reloaded module.
+fn bound_by_hmr_cancel<S>(
+    input: S,
+    mut token: RenderCancelToken,
+    content_type: ChunkedContentType,
+    closing: Bytes,
+) -> impl Stream<Item = Result<Bytes, Error>> + Send + 'static
+where
+    S: Stream<Item = Result<Bytes, Error>> + Send + 'static,
+{
+    let cancelled = async move {
+        token.cancelled().await;
+        tracing::info!("Module changed during render; closing stream for reload");
+    };
+    end_stream_on(
+        input,
+        Some(cancelled),
+        content_type,
+        closing,
+        "Module reloaded during render",
+        "RSC render cancelled by module reload",
+    )
+}
+
+/// Forwards `input` until `interrupt` resolves, then ends the stream early. HTML
+/// gets an error chunk followed by `closing`, so the document is still well-formed;
+/// RSC gets a stream error. `None` passes `input` through untouched.
+fn end_stream_on<S, F>(
+    input: S,
+    interrupt: Option<F>,
+    content_type: ChunkedContentType,
+    closing: Bytes,
+    html_message: &'static str,
+    rsc_message: &'static str,
+) -> impl Stream<Item = Result<Bytes, Error>> + Send + 'static
+where
+    S: Stream<Item = Result<Bytes, Error>> + Send + 'static,
+    F: Future<Output = ()> + Send + 'static,
+{
+    async_stream::stream! {
+        let mut input = Box::pin(input);
+
+        let Some(interrupt) = interrupt else {
+            while let Some(item) = input.next().await {
+                yield item;
+            }
+            return;
+        };
+        tokio::pin!(interrupt);
+
+        loop {
+            tokio::select! {
+                biased;
+                item = input.next() => match item {
+                    Some(item) => yield item,
+                    None => break,
+                },
+                () = &mut interrupt => {
+                    match content_type {
+                        ChunkedContentType::Html => {
+                            yield Ok(chunked_stream_error_chunk(html_message));
+                            if !closing.is_empty() {
+                                yield Ok(closing);
+                            }
+                        }
+                        ChunkedContentType::RscFlight => {
+                            yield Err(Error::other(rsc_message));
+                        }
+                    }
+                    break;
+                }
+            }
+        }
+    }
+}
+
 fn chunked_stream_stall_timeout_ms() -> u64 {
     env::var("RARI_STREAMING_STALL_TIMEOUT_MS")
         .ok()
@@ -839,6 +1173,7 @@ pub async fn render_synchronous(
     route_match: AppRouteMatch,
     context: LayoutRenderContext,
     accept_encoding: Option<&str>,
+    bounds: RenderBounds,
 ) -> Result<Response, StatusCode> {
     let layout_renderer = LayoutRenderer::with_shared_cache(
         Arc::clone(&state.renderer),
@@ -851,8 +1186,14 @@ pub async fn render_synchronous(
 
     let is_not_found = route_match.not_found.is_some();
 
-    match layout_renderer
-        .render_route_with_streaming(&route_match, &context, Some(request_context), false, None)
+    match bounds
+        .within_deadline(layout_renderer.render_route_with_streaming(
+            &route_match,
+            &context,
+            Some(request_context),
+            false,
+            None,
+        ))
         .await
     {
         Ok(render_result) => match render_result {
@@ -900,6 +1241,7 @@ pub async fn render_synchronous(
                 is_not_found,
                 accept_encoding,
                 None,
+                bounds,
             )),
             RenderResult::StaticBinary(bytes) => {
                 let html_content = String::from_utf8_lossy(&bytes).into_owned();
@@ -923,7 +1265,15 @@ pub async fn render_synchronous(
             }
         },
         Err(e) => {
+            if let Some(response) = render_redirect_response(&e, &state.config) {
+                return Ok(response);
+            }
             tracing::error!("Synchronous rendering failed: {}", e);
+            if let Some(response) =
+                api_route_error_response(&e, &state.config, &route_match.pathname)
+            {
+                return Ok(response);
+            }
             render_fallback_html(&state, is_not_found).await
         }
     }
@@ -936,6 +1286,7 @@ pub async fn render_streaming_with_layout(
     layout_renderer: &LayoutRenderer,
     accept_encoding: Option<&str>,
     metadata_rx: Option<oneshot::Receiver<Option<PageMetadata>>>,
+    bounds: RenderBounds,
 ) -> Result<Response, StatusCode> {
     let layout_count = route_match.layouts.len();
     let is_not_found = route_match.not_found.is_some();
@@ -947,12 +1298,21 @@ pub async fn render_streaming_with_layout(
 
     // Keep metadata_rx for HTTP injection / static wrap. Do not pass it into
     // Fizz setup -- try_recv there would drop a still-pending receiver.
-    let render_result = match layout_renderer
-        .render_route_with_streaming(&route_match, &context, Some(request_context), false, None)
+    let render_result = match bounds
+        .within_deadline(layout_renderer.render_route_with_streaming(
+            &route_match,
+            &context,
+            Some(request_context),
+            false,
+            None,
+        ))
         .await
     {
         Ok(result) => result,
         Err(e) => {
+            if let Some(response) = render_redirect_response(&e, &state.config) {
+                return Ok(response);
+            }
             tracing::error!(
                 "Failed to render route for streaming '{}': {}",
                 route_match.route.path,
@@ -974,7 +1334,7 @@ pub async fn render_streaming_with_layout(
                 );
             }
 
-            return render_synchronous(state, route_match, context, accept_encoding).await;
+            return render_synchronous(state, route_match, context, accept_encoding, bounds).await;
         }
     };
 
@@ -994,6 +1354,7 @@ pub async fn render_streaming_with_layout(
             is_not_found,
             accept_encoding,
             metadata_rx,
+            bounds,
         )),
         RenderResult::Static(html) => {
             use crate::server::compression::compress_body;
@@ -1054,6 +1415,52 @@ pub async fn render_streaming_with_layout(
     }
 }
 
+fn append_preload_links(headers: &mut HeaderMap, config: &Config, route_match: &AppRouteMatch) {
+    if !config.server.preload_link_headers {
+        return;
+    }
+
+    for link in RscHtmlRenderer::preload_links(route_match, &config.server.base_path) {
+        if let Ok(value) = HeaderValue::from_str(&link) {
+            headers.append(LINK, value);
+        }
+    }
+}
+
+/// Turn a render-time [`RariError::Redirect`] into a 3xx response, provided the
+/// location passes the same checks as server action redirects. RSC navigations
+/// get the same response: the client router follows it and adopts the final URL.
+fn render_redirect_response(error: &RariError, config: &Config) -> Option<Response> {
+    let (location, status) = error.redirect_target()?;
+    let location = match validate_redirect_url(location, &config.redirect_config()) {
+        Ok(location) => location,
+        Err(e) => {
+            tracing::warn!("Ignoring render redirect to {location:?}: {e}");
+            return None;
+        }
+    };
+    let status = StatusCode::from_u16(status).unwrap_or(StatusCode::TEMPORARY_REDIRECT);
+
+    #[expect(clippy::expect_used, reason = "Response::builder() with valid components never fails")]
+    Some(
+        Response::builder()
+            .status(status)
+            .header("location", location)
+            .header("cache-control", "no-store")
+            .header("vary", "Accept")
+            .body(Body::empty())
+            .expect("Valid redirect response"),
+    )
+}
+
+/// JSON error for a failed render on a configured API route, instead of the
+/// HTML fallback shell.
+fn api_route_error_response(error: &RariError, config: &Config, path: &str) -> Option<Response> {
+    config
+        .is_api_route(path)
+        .then(|| error_response::api_json_response(error, config.is_development()))
+}
+
 fn fallback_html_response(html: Bytes, is_not_found: bool) -> Response {
     let status_code = if is_not_found { StatusCode::NOT_FOUND } else { StatusCode::OK };
     #[expect(clippy::expect_used, reason = "Response::builder() with valid components never fails")]
@@ -1069,7 +1476,7 @@ pub async fn render_fallback_html(
     state: &ServerState,
     is_not_found: bool,
 ) -> Result<Response, StatusCode> {
-    let index_path = if state.config.is_development() {
+    let index_path = if state.config.uses_source_template() {
         let root_index = PathBuf::from("index.html");
         if fs::try_exists(&root_index).await.unwrap_or(false) {
             root_index
@@ -1098,7 +1505,7 @@ pub async fn render_fallback_html(
                 html_content
             };
 
-            if state.config.is_development() {
+            if state.config.pretty_print_html() {
                 final_html = pretty_print_html(&final_html);
             }
 
@@ -1159,6 +1566,71 @@ pub async fn render_fallback_html(
     Ok(fallback_html_response(Bytes::from(error_html), is_not_found))
 }
 
+/// Status and headers for a cached SSR page. `GET` adds the body and its
+/// encoding; `HEAD` sends it as is, so both report the same ETag and
+/// cache-control.
+fn cached_ssr_response_builder(
+    state: &ServerState,
+    path: &str,
+    cached: &response::CachedResponse,
+    is_not_found: bool,
+) -> axum::http::response::Builder {
+    let status = if is_not_found { StatusCode::NOT_FOUND } else { StatusCode::OK };
+
+    let mut builder = Response::builder()
+        .status(status)
+        .header(
+            "content-type",
+            state.config.content_type_for_route(path).unwrap_or("text/html; charset=utf-8"),
+        )
+        .header("vary", merge_vary_with_accept(cached.headers.get("vary")))
+        .header("x-cache", "HIT");
+
+    if let Some(etag) = &cached.metadata.etag {
+        builder = builder.header("etag", etag);
+    }
+
+    for (key, value) in &cached.headers {
+        if !matches!(
+            key.as_str(),
+            "vary" | "content-encoding" | "content-length" | "content-type" | "etag"
+        ) {
+            builder = builder.header(key, value);
+        }
+    }
+
+    builder
+}
+
+/// Header-only response for a `HEAD` page request, built without rendering. A
+/// cached render supplies its headers and ETag; on a miss the route's
+/// cache-control and content type are sent with no ETag, since that needs the body.
+async fn head_response(
+    state: &ServerState,
+    path: &str,
+    query_params_ref: Option<&FxHashMap<String, String>>,
+    cookie_header: Option<&str>,
+    is_not_found: bool,
+) -> Response {
+    let cache_key = response_cache_key(path, query_params_ref, None, cookie_header);
+
+    let builder = match state.response_cache.get(&cache_key).await {
+        Some(cached) => cached_ssr_response_builder(state, path, &cached, is_not_found),
+        None => Response::builder()
+            .status(if is_not_found { StatusCode::NOT_FOUND } else { StatusCode::OK })
+            .header(
+                "content-type",
+                state.config.content_type_for_route(path).unwrap_or("text/html; charset=utf-8"),
+            )
+            .header("cache-control", state.config.cache_control_for_route(path).as_str())
+            .header("vary", static_html_vary_header(cookie_header))
+            .header("x-cache", "MISS"),
+    };
+
+    #[expect(clippy::expect_used, reason = "Response::builder() with valid components never fails")]
+    builder.body(Body::empty()).expect("Valid HEAD response")
+}
+
 #[axum::debug_handler]
 #[expect(
     clippy::implicit_hasher,
@@ -1166,9 +1638,11 @@ pub async fn render_fallback_html(
 )]
 pub async fn handle_app_route(
     State(state): State<ServerState>,
+    method: Method,
     uri: Uri,
     Query(query_params): Query<FxHashMap<String, String>>,
     headers: HeaderMap,
+    extensions: Extensions,
 ) -> Result<Response, StatusCode> {
     let path = uri.path();
 
@@ -1187,10 +1661,12 @@ pub async fn handle_app_route(
                 }
             }
 
-            if let Ok(file_path) =
-                validate_safe_path(state.config.public_dir(), path_without_leading_slash).await
-                && let Ok(metadata) = fs::metadata(&file_path).await
-                && metadata.is_file()
+            if let Some(file_path) = resolve_public_file(
+                &state.config.static_files,
+                state.config.public_dir(),
+                path_without_leading_slash,
+            )
+            .await
             {
                 match fs::read(&file_path).await {
                     Ok(content) => {
@@ -1303,6 +1779,7 @@ pub async fn handle_app_route(
         route_match.pathname.clone(),
     );
     context.template_navigation_id = utils::http::parse_navigation_id(&context.headers);
+    context.client_ip = utils::http::peer_ip(&extensions);
 
     let layout_renderer = LayoutRenderer::with_shared_cache(
         Arc::clone(&state.renderer),
@@ -1323,6 +1800,34 @@ pub async fn handle_app_route(
         }
     }
 
+    if method == Method::HEAD
+        && state.config.server.head_skip_render
+        && matches!(render_mode, RenderMode::Ssr)
+    {
+        return Ok(head_response(
+            &state,
+            path,
+            query_params_ref,
+            cookie_header,
+            route_match.not_found.is_some(),
+        )
+        .await);
+    }
+
+    // One wall-clock budget for the whole request, queue wait included.
+    let deadline = state.config.server.render_deadline().map(|limit| time::Instant::now() + limit);
+    // Buffered renders release the slot when this handler returns; streamed
+    // bodies hold a clone until the last chunk is sent.
+    let bounds = RenderBounds::new(
+        state.render_scheduler.acquire(state.config.render_priority_for_route(path)).await,
+    )
+    .with_deadline(deadline);
+    let bounds = if state.hmr_render_cancellation.is_enabled() {
+        bounds.with_component_ids(route_component_ids(&route_match))
+    } else {
+        bounds
+    };
+
     match render_mode {
         RenderMode::RscNavigation => {
             let use_streaming = should_use_streaming(&route_match, &state.config);
@@ -1335,6 +1840,7 @@ pub async fn handle_app_route(
                     route_match,
                     context,
                     accept_encoding,
+                    bounds,
                 )
                 .await;
             }
@@ -1375,8 +1881,12 @@ pub async fn handle_app_route(
             let metadata_rx =
                 spawn_page_metadata(state.clone(), route_match.clone(), context.clone());
 
-            match layout_renderer
-                .render_route_by_mode(&route_match, &context, Some(Arc::clone(&request_context)))
+            match bounds
+                .within_deadline(layout_renderer.render_route_by_mode(
+                    &route_match,
+                    &context,
+                    Some(Arc::clone(&request_context)),
+                ))
                 .await
             {
                 Ok(rsc_flight_protocol) => {
@@ -1447,6 +1957,9 @@ pub async fn handle_app_route(
                         .expect("Valid RSC response"))
                 }
                 Err(e) => {
+                    if let Some(response) = render_redirect_response(&e, &state.config) {
+                        return Ok(response);
+                    }
                     tracing::error!("Failed to render RSC: {}", e);
                     Err(error_response::status(&e))
                 }
@@ -1475,14 +1988,6 @@ pub async fn handle_app_route(
                         .expect("Valid 304 response"));
                 }
 
-                let status_code = if route_match.not_found.is_some() {
-                    StatusCode::NOT_FOUND
-                } else {
-                    StatusCode::OK
-                };
-
-                let merged_vary = merge_vary_with_accept(cached.headers.get("vary"));
-
                 let encoding = CompressionEncoding::from_accept_encoding(accept_encoding);
 
                 let (body_bytes, actual_encoding) =
@@ -1512,31 +2017,17 @@ pub async fn handle_app_route(
                         (compressed, actual_enc)
                     };
 
-                let mut response_builder = Response::builder()
-                    .status(status_code)
-                    .header("content-type", "text/html; charset=utf-8")
-                    .header("vary", merged_vary)
-                    .header("x-cache", "HIT");
+                let mut response_builder = cached_ssr_response_builder(
+                    &state,
+                    path,
+                    &cached,
+                    route_match.not_found.is_some(),
+                );
 
                 if let Some(encoding_header) = actual_encoding.as_header_value() {
                     response_builder = response_builder.header("content-encoding", encoding_header);
                 }
 
-                if let Some(etag) = &cached.metadata.etag {
-                    response_builder = response_builder.header("etag", etag);
-                }
-
-                for (key, value) in &cached.headers {
-                    if key.as_str() != "vary"
-                        && key.as_str() != "content-encoding"
-                        && key.as_str() != "content-length"
-                        && key.as_str() != "content-type"
-                        && key.as_str() != "etag"
-                    {
-                        response_builder = response_builder.header(key, value);
-                    }
-                }
-
                 #[expect(
                     clippy::expect_used,
                     reason = "Response::builder() with valid components never fails"
@@ -1556,7 +2047,7 @@ pub async fn handle_app_route(
                 let block_metadata =
                     is_html_limited_bot(user_agent, state.config.html_limited_bots_regex.as_ref());
 
-                let response = if block_metadata {
+                let mut response = if block_metadata {
                     let mut context = context.clone();
                     let metadata = collect_page_metadata(&state, &route_match, &context).await;
                     apply_blocking_streaming_metadata(
@@ -1570,6 +2061,7 @@ pub async fn handle_app_route(
                         context,
                         accept_encoding,
                         None,
+                        bounds.clone(),
                     )
                     .await?
                 } else {
@@ -1584,6 +2076,7 @@ pub async fn handle_app_route(
                         context.clone(),
                         accept_encoding,
                         Some(metadata_rx),
+                        bounds.clone(),
                     )
                     .await?;
 
@@ -1599,6 +2092,7 @@ pub async fn handle_app_route(
 
                     response
                 };
+                append_preload_links(response.headers_mut(), &state.config, &route_match);
 
                 if (response.status() == StatusCode::OK
                     || response.status() == StatusCode::NOT_FOUND)
@@ -1732,18 +2226,24 @@ pub async fn handle_app_route(
             let metadata_rx =
                 spawn_page_metadata(state.clone(), route_match.clone(), context.clone());
 
-            let render_result = match layout_renderer
-                .render_route_with_streaming(
+            let render_result = match bounds
+                .within_deadline(layout_renderer.render_route_with_streaming(
                     &route_match,
                     &context,
                     Some(Arc::clone(&request_context)),
                     false,
                     None,
-                )
+                ))
                 .await
             {
                 Ok(result) => result,
                 Err(e) => {
+                    if let Some(response) = render_redirect_response(&e, &state.config) {
+                        return Ok(response);
+                    }
+                    if let Some(response) = api_route_error_response(&e, &state.config, path) {
+                        return Ok(response);
+                    }
                     tracing::error!("Direct HTML rendering failed: {}, falling back to shell", e);
                     return render_fallback_html(&state, route_match.not_found.is_some()).await;
                 }
@@ -1784,7 +2284,10 @@ pub async fn handle_app_route(
                     closing,
                     mut chunks,
                 } => {
-                    let html = match drain_chunked_stream(shell, closing, &mut chunks).await {
+                    let html = match bounds
+                        .within_deadline(drain_chunked_stream(shell, closing, &mut chunks))
+                        .await
+                    {
                         Ok(html) => html,
                         Err(error) => {
                             tracing::error!(
@@ -1794,6 +2297,7 @@ pub async fn handle_app_route(
                                 .await;
                         }
                     };
+                    let html = apply_base_path(&html, &state.config.server.base_path);
                     let final_html =
                         wrap_html_with_metadata(html, context.metadata.as_ref(), &state);
                     let etag = response::ResponseCache::generate_etag(final_html.as_bytes());
@@ -1819,12 +2323,19 @@ pub async fn handle_app_route(
 
             let mut response_builder = Response::builder()
                 .status(status_code)
-                .header("content-type", "text/html; charset=utf-8")
+                .header(
+                    "content-type",
+                    state.config.content_type_for_route(path).unwrap_or("text/html; charset=utf-8"),
+                )
                 .header("etag", &etag)
                 .header("vary", static_html_vary_header(cookie_header))
                 .header("x-cache", "MISS");
 
             let mut response_headers = HeaderMap::new();
+            append_preload_links(&mut response_headers, &state.config, &route_match);
+            if let Some(headers) = response_builder.headers_mut() {
+                headers.extend(response_headers.clone());
+            }
 
             response_builder = response_builder.header("cache-control", cache_control_value);
             if let Ok(header_value) = HeaderValue::from_str(cache_control_value) {
@@ -1871,7 +2382,11 @@ pub async fn handle_app_route(
                             br: compressed_br.clone(),
                             zstd: compressed_zstd.clone(),
                             etag: etag.clone(),
-                            content_type: "text/html; charset=utf-8".to_string(),
+                            content_type: state
+                                .config
+                                .content_type_for_route(path)
+                                .unwrap_or("text/html; charset=utf-8")
+                                .to_string(),
                             cache_control: cache_control_value.to_string(),
                             is_not_found: route_match.not_found.is_some(),
                         }),
@@ -1936,8 +2451,10 @@ mod tests {
             cache::{
                 handler::{CacheHandlerRegistry, MemoryCacheHandler},
                 response::{CacheConfig, ResponseCache, StaticFastCache},
+                revalidate::RevalidateRateLimiter,
             },
-            config::Mode,
+            config::{Mode, RenderPriority},
+            rendering::{hmr_cancel::HmrRenderCancellation, render_queue::RenderScheduler},
         },
     };
 
@@ -1984,9 +2501,19 @@ mod tests {
             image_optimizer: None,
             cache_registry,
             image_handler,
+            render_scheduler: Arc::new(RenderScheduler::new(0)),
+            hmr_render_cancellation: Arc::new(HmrRenderCancellation::new(false)),
+            revalidate_limiter: Arc::new(RevalidateRateLimiter::new(0)),
         }
     }
 
+    async fn render_bounds(state: &Arc<ServerState>) -> RenderBounds {
+        let deadline =
+            state.config.server.render_deadline().map(|limit| time::Instant::now() + limit);
+        RenderBounds::new(state.render_scheduler.acquire(RenderPriority::High).await)
+            .with_deadline(deadline)
+    }
+
     #[tokio::test]
     async fn test_render_fallback_html_cache_hit_returns_not_found() {
         let public_dir = env::temp_dir().join(format!(
@@ -2006,4 +2533,470 @@ mod tests {
 
         let _ = fs::remove_dir_all(public_dir);
     }
+
+    #[tokio::test]
+    async fn test_head_response_has_headers_without_body() {
+        let mut state =
+            production_state_with_html_cache(FallbackHtmlCache::default(), PathBuf::from("."));
+        let mut config = Config::new(Mode::Production);
+        config.caching.routes.insert("/about".to_string(), "public, max-age=30".to_string());
+        state.config = Arc::new(config);
+
+        let miss = head_response(&state, "/about", None, None, false).await;
+        assert_eq!(miss.status(), StatusCode::OK);
+        assert_eq!(miss.headers()["content-type"], "text/html; charset=utf-8");
+        assert_eq!(miss.headers()[CACHE_CONTROL], "public, max-age=30");
+        assert_eq!(miss.headers()["x-cache"], "MISS");
+        assert!(miss.headers().get("etag").is_none());
+        let miss_body = body::to_bytes(miss.into_body(), usize::MAX).await.expect("head body");
+        assert!(miss_body.is_empty());
+        assert!(
+            state
+                .response_cache
+                .get(&response_cache_key("/about", None, None, None))
+                .await
+                .is_none()
+        );
+
+        let mut cached_headers = HeaderMap::new();
+        cached_headers.insert("content-type", HeaderValue::from_static("text/html; charset=utf-8"));
+        cached_headers.insert(CACHE_CONTROL, HeaderValue::from_static("public, max-age=60"));
+        let cached = response::CachedResponse {
+            body: Bytes::from("<html>about</html>"),
+            headers: cached_headers,
+            metadata: response::CacheMetadata {
+                cached_at: Instant::now(),
+                ttl: 60,
+                etag: Some("\"abc\"".to_string()),
+                tags: vec![],
+            },
+            compressed_zstd: None,
+            compressed_br: None,
+            compressed_gzip: None,
+        };
+        let cache_key = response_cache_key("/about", None, None, None);
+        state.response_cache.set(cache_key.clone(), cached).await;
+
+        let head = head_response(&state, "/about", None, None, false).await;
+        assert_eq!(head.status(), StatusCode::OK);
+        assert_eq!(head.headers()["content-type"], "text/html; charset=utf-8");
+        assert_eq!(head.headers()["x-cache"], "HIT");
+        assert_eq!(head.headers()["etag"], "\"abc\"");
+        assert_eq!(head.headers()[CACHE_CONTROL], "public, max-age=60");
+        let head_body = body::to_bytes(head.into_body(), usize::MAX).await.expect("head body");
+        assert!(head_body.is_empty());
+
+        let get = state.response_cache.get(&cache_key).await.expect("cached GET render");
+        assert_eq!(get.metadata.etag.as_deref(), Some("\"abc\""));
+        assert_eq!(get.body, Bytes::from("<html>about</html>"));
+    }
+
+    #[test]
+    fn test_render_redirect_response_uses_typed_status() {
+        let config = Config::new(Mode::Production);
+
+        let response = render_redirect_response(&RariError::redirect("/login", 303), &config)
+            .expect("redirect response");
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(response.headers()["location"], "/login");
+        assert_eq!(response.headers()["vary"], "Accept");
+
+        assert!(
+            render_redirect_response(&RariError::redirect("https://evil.example/", 307), &config)
+                .is_none()
+        );
+        assert!(render_redirect_response(&RariError::internal("/login"), &config).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_api_route_render_failure_returns_json() {
+        let mut config = Config::new(Mode::Production);
+        config.api_routes.routes = vec!["/api/*".to_string()];
+        config.compile_route_patterns();
+        let error = RariError::js_execution("component threw: secret detail");
+
+        let response =
+            api_route_error_response(&error, &config, "/api/users").expect("api route response");
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(response.headers()["content-type"], "application/json");
+
+        let body = body::to_bytes(response.into_body(), usize::MAX).await.expect("json body");
+        let json: serde_json::Value = serde_json::from_slice(&body).expect("valid json");
+        assert_eq!(json["code"], error.code());
+        assert!(json.get("details").is_none());
+        assert!(!String::from_utf8_lossy(&body).contains("secret detail"));
+
+        assert!(api_route_error_response(&error, &config, "/about").is_none());
+    }
+
+    #[test]
+    fn test_base_url_honors_forwarded_headers_from_trusted_peer() {
+        let mut config = Config::new(Mode::Production);
+        config.server.trusted_proxies = vec!["10.0.0.1".parse().expect("valid proxy")];
+
+        let mut headers = FxHashMap::default();
+        headers.insert("host".to_string(), "internal:3000".to_string());
+        headers.insert("x-forwarded-host".to_string(), "example.com".to_string());
+        headers.insert("x-forwarded-proto".to_string(), "https".to_string());
+        let mut context = create_layout_context(
+            FxHashMap::default(),
+            FxHashMap::default(),
+            headers,
+            "/".to_string(),
+        );
+
+        context.client_ip = Some("10.0.0.1".parse().expect("valid ip"));
+        assert_eq!(get_base_url_from_context(&context, &config), "https://example.com");
+
+        context.client_ip = Some("203.0.113.7".parse().expect("valid ip"));
+        assert_eq!(get_base_url_from_context(&context, &config), "https://internal:3000");
+    }
+
+    #[tokio::test]
+    async fn test_streaming_response_sets_route_cache_control_before_body() {
+        let mut state =
+            production_state_with_html_cache(FallbackHtmlCache::default(), PathBuf::from("."));
+        let mut config = Config::new(Mode::Production);
+        config.caching.routes.insert("/blog/*".to_string(), "public, max-age=60".to_string());
+        config.caching.routes.insert("/blog/pinned".to_string(), "private, max-age=5".to_string());
+        let server_components = config.caching.server_components.clone();
+        state.config = Arc::new(config);
+        let state = Arc::new(state);
+
+        for (path, expected) in [
+            ("/blog/pinned", "private, max-age=5"),
+            ("/blog/post-1", "public, max-age=60"),
+            ("/about", server_components.as_str()),
+        ] {
+            // The sender stays open, so no body chunk has been produced yet.
+            let (_tx, rx) = tokio::sync::mpsc::channel(1);
+            let context = create_layout_context(
+                FxHashMap::default(),
+                FxHashMap::default(),
+                FxHashMap::default(),
+                path.to_string(),
+            );
+            let response = render_chunked_response(
+                &state,
+                &context,
+                ChunkedContentType::Html,
+                Bytes::from_static(b"<html><body>"),
+                Bytes::from_static(b"</body></html>"),
+                rx,
+                false,
+                None,
+                None,
+                render_bounds(&state).await,
+            );
+
+            assert_eq!(response.headers()[CACHE_CONTROL], expected, "path {path}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_render_deadline_closes_stream_before_slow_boundary() {
+        let input = async_stream::stream! {
+            yield Ok::<_, Error>(Bytes::from_static(b"<!DOCTYPE html><div id=root>shell</div>"));
+            time::sleep(Duration::from_millis(500)).await;
+            yield Ok(Bytes::from_static(b"<template>boundary</template>"));
+        };
+
+        let bounded = bound_by_render_deadline(
+            input,
+            Some(time::Instant::now() + Duration::from_millis(50)),
+            ChunkedContentType::Html,
+            Bytes::from_static(b"</body></html>"),
+        );
+        let chunks: Vec<_> = bounded.collect().await;
+
+        assert_eq!(chunks.len(), 3);
+        let body: String = chunks
+            .into_iter()
+            .map(|chunk| String::from_utf8(chunk.expect("html chunk").to_vec()).expect("utf8"))
+            .collect();
+        assert!(body.contains("shell"));
+        assert!(body.contains("Render deadline exceeded"));
+        assert!(!body.contains("boundary"));
+        assert!(body.ends_with("</body></html>"));
+    }
+
+    #[tokio::test]
+    async fn test_render_deadline_closes_streamed_html_document() {
+        let mut state =
+            production_state_with_html_cache(FallbackHtmlCache::default(), PathBuf::from("."));
+        let mut config = Config::new(Mode::Production);
+        config.server.max_render_deadline_ms = 50;
+        state.config = Arc::new(config);
+        let state = Arc::new(state);
+
+        // The sender stays open past the deadline, like a Suspense boundary that never resolves.
+        let (tx, rx) = tokio::sync::mpsc::channel(4);
+        tx.send(Ok(b"<div id=root>shell</div>".to_vec())).await.expect("send chunk");
+        let context = create_layout_context(
+            FxHashMap::default(),
+            FxHashMap::default(),
+            FxHashMap::default(),
+            "/slow".to_string(),
+        );
+        let response = render_chunked_response(
+            &state,
+            &context,
+            ChunkedContentType::Html,
+            Bytes::from_static(b"<!DOCTYPE html><html><body>"),
+            Bytes::from_static(b"</body></html>"),
+            rx,
+            false,
+            None,
+            None,
+            render_bounds(&state).await,
+        );
+
+        let body = body::to_bytes(response.into_body(), usize::MAX).await.expect("html body");
+        let body = String::from_utf8(body.to_vec()).expect("utf8");
+        assert!(body.starts_with("<!DOCTYPE html><html><body>"));
+        assert!(body.contains("shell"));
+        assert!(body.contains("Render deadline exceeded"));
+        assert!(body.ends_with("</body></html>"));
+        drop(tx);
+    }
+
+    #[tokio::test]
+    async fn test_render_deadline_counts_time_before_streaming() {
+        let state = Arc::new(production_state_with_html_cache(
+            FallbackHtmlCache::default(),
+            PathBuf::from("."),
+        ));
+        let bounds = render_bounds(&state)
+            .await
+            .with_deadline(Some(time::Instant::now() + Duration::from_millis(100)));
+        // A slow shell render spends the whole budget before the body streams.
+        time::sleep(Duration::from_millis(120)).await;
+
+        let (tx, rx) = tokio::sync::mpsc::channel(4);
+        let context = create_layout_context(
+            FxHashMap::default(),
+            FxHashMap::default(),
+            FxHashMap::default(),
+            "/slow".to_string(),
+        );
+        let response = render_chunked_response(
+            &state,
+            &context,
+            ChunkedContentType::Html,
+            Bytes::from_static(b"<!DOCTYPE html><html><body>"),
+            Bytes::from_static(b"</body></html>"),
+            rx,
+            false,
+            None,
+            None,
+            bounds,
+        );
+
+        let body = time::timeout(
+            Duration::from_millis(50),
+            body::to_bytes(response.into_body(), usize::MAX),
+        )
+        .await
+        .expect("deadline already passed, so the stream ends at once")
+        .expect("html body");
+        let body = String::from_utf8(body.to_vec()).expect("utf8");
+        assert!(body.contains("Render deadline exceeded"));
+        assert!(body.ends_with("</body></html>"));
+        drop(tx);
+    }
+
+    #[tokio::test]
+    async fn test_render_deadline_bounds_buffered_render() {
+        let state = Arc::new(production_state_with_html_cache(
+            FallbackHtmlCache::default(),
+            PathBuf::from("."),
+        ));
+        let bounds = render_bounds(&state)
+            .await
+            .with_deadline(Some(time::Instant::now() + Duration::from_millis(20)));
+
+        let result = bounds
+            .within_deadline(async {
+                time::sleep(Duration::from_millis(500)).await;
+                Ok::<_, RariError>("late")
+            })
+            .await;
+        assert!(matches!(result, Err(RariError::Timeout(..))));
+
+        let fast = render_bounds(&state).await.within_deadline(async { Ok("done") }).await;
+        assert_eq!(fast.expect("no deadline configured"), "done");
+    }
+
+    #[tokio::test]
+    async fn test_streaming_render_holds_queue_slot_until_body_completes() {
+        let mut state =
+            production_state_with_html_cache(FallbackHtmlCache::default(), PathBuf::from("."));
+        state.render_scheduler = Arc::new(RenderScheduler::new(1));
+        let state = Arc::new(state);
+
+        let (tx, rx) = tokio::sync::mpsc::channel(4);
+        let context = create_layout_context(
+            FxHashMap::default(),
+            FxHashMap::default(),
+            FxHashMap::default(),
+            "/report".to_string(),
+        );
+        let response = render_chunked_response(
+            &state,
+            &context,
+            ChunkedContentType::Html,
+            Bytes::from_static(b"<!DOCTYPE html><html><body>"),
+            Bytes::from_static(b"</body></html>"),
+            rx,
+            false,
+            None,
+            None,
+            render_bounds(&state).await,
+        );
+
+        // The response head is ready but the body is still rendering.
+        assert_eq!(state.render_scheduler.active(), 1);
+        let waiter = tokio::spawn({
+            let scheduler = Arc::clone(&state.render_scheduler);
+            async move {
+                let _permit = scheduler.acquire(RenderPriority::High).await;
+            }
+        });
+        while state.render_scheduler.queued() < 1 {
+            tokio::task::yield_now().await;
+        }
+
+        tx.send(Ok(b"<div>report</div>".to_vec())).await.expect("send chunk");
+        drop(tx);
+        let body = body::to_bytes(response.into_body(), usize::MAX).await.expect("html body");
+        assert!(String::from_utf8_lossy(&body).contains("report"));
+
+        waiter.await.expect("queued render runs once the stream ends");
+        assert_eq!(state.render_scheduler.active(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_streaming_html_applies_base_path_to_every_chunk() {
+        let mut state =
+            production_state_with_html_cache(FallbackHtmlCache::default(), PathBuf::from("."));
+        let mut config = Config::new(Mode::Production);
+        config.server.base_path = "/app".to_string();
+        state.config = Arc::new(config);
+        let state = Arc::new(state);
+
+        let (tx, rx) = tokio::sync::mpsc::channel(4);
+        tx.send(Ok(br##"<a href="#section">Jump</a><script src="/as"##.to_vec()))
+            .await
+            .expect("send chunk");
+        tx.send(Ok(br##"sets/late.js"></script>"##.to_vec())).await.expect("send chunk");
+        drop(tx);
+        let context = create_layout_context(
+            FxHashMap::default(),
+            FxHashMap::default(),
+            FxHashMap::default(),
+            "/".to_string(),
+        );
+        let response = render_chunked_response(
+            &state,
+            &context,
+            ChunkedContentType::Html,
+            Bytes::from_static(
+                br#"<!DOCTYPE html><html><head><script type="module" src="/assets/app.js"></script></head><body>"#,
+            ),
+            Bytes::from_static(b"</body></html>"),
+            rx,
+            false,
+            None,
+            None,
+            render_bounds(&state).await,
+        );
+
+        let body = body::to_bytes(response.into_body(), usize::MAX).await.expect("html body");
+        let body = String::from_utf8(body.to_vec()).expect("utf8");
+        assert!(!body.contains("<base "));
+        assert!(body.contains(r#"src="/app/assets/app.js""#));
+        assert!(body.contains(r#"<script src="/app/assets/late.js"></script>"#));
+        assert!(body.contains(r##"<a href="#section">Jump</a>"##));
+    }
+
+    #[tokio::test]
+    async fn test_module_change_cancels_streaming_render() {
+        let cancellation = Arc::new(HmrRenderCancellation::new(true));
+        let input = async_stream::stream! {
+            yield Ok::<_, Error>(Bytes::from_static(b"<!DOCTYPE html><div id=root>shell</div>"));
+            time::sleep(Duration::from_millis(500)).await;
+            yield Ok(Bytes::from_static(b"<template>stale</template>"));
+        };
+
+        let bounded = bound_by_hmr_cancel(
+            input,
+            cancellation.token(vec!["app/page_73d7a23e".to_string()]),
+            ChunkedContentType::Html,
+            Bytes::from_static(b"</body></html>"),
+        );
+        tokio::spawn({
+            let cancellation = Arc::clone(&cancellation);
+            async move {
+                time::sleep(Duration::from_millis(50)).await;
+                cancellation.cancel_active("app/page_73d7a23e");
+            }
+        });
+        let chunks: Vec<_> = bounded.collect().await;
+
+        assert_eq!(chunks.len(), 3);
+        let body: String = chunks
+            .into_iter()
+            .map(|chunk| String::from_utf8(chunk.expect("html chunk").to_vec()).expect("utf8"))
+            .collect();
+        assert!(body.contains("shell"));
+        assert!(body.contains("Module reloaded during render"));
+        assert!(!body.contains("stale"));
+        assert!(body.ends_with("</body></html>"));
+        assert_eq!(cancellation.active(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_small_chunks_coalesce_into_one_write() {
+        let input = async_stream::stream! {
+            yield Ok::<_, Error>(Bytes::from_static(b"<!DOCTYPE html>"));
+            for row in ["1:a\n", "2:b\n", "3:c\n", "4:d\n"] {
+                yield Ok(Bytes::from_static(row.as_bytes()));
+            }
+            time::sleep(Duration::from_millis(200)).await;
+            yield Ok(Bytes::from_static(b"</html>"));
+        };
+
+        let coalesced = coalesce_small_chunks(input, Some((1024, Duration::from_millis(20))));
+        let chunks: Vec<_> = coalesced.map(|chunk| chunk.expect("chunk")).collect().await;
+
+        assert_eq!(
+            chunks,
+            vec![
+                Bytes::from_static(b"<!DOCTYPE html>"),
+                Bytes::from_static(b"1:a\n2:b\n3:c\n4:d\n"),
+                Bytes::from_static(b"</html>"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_render_deadline_errors_rsc_stream() {
+        let input = async_stream::stream! {
+            yield Ok::<_, Error>(Bytes::from_static(b"0:[]\n"));
+            time::sleep(Duration::from_millis(500)).await;
+            yield Ok(Bytes::from_static(b"1:[]\n"));
+        };
+
+        let bounded = bound_by_render_deadline(
+            input,
+            Some(time::Instant::now() + Duration::from_millis(50)),
+            ChunkedContentType::RscFlight,
+            Bytes::new(),
+        );
+        let chunks: Vec<_> = bounded.collect().await;
+
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks[0].is_ok());
+        assert!(chunks[1].is_err());
+    }
 }
@@ -11,5 +11,5 @@ pub use api_routes::{ApiRouteEntry, ApiRouteHandler, ApiRouteManifest, ApiRouteM
 pub use app_router::{
     AppRouteEntry, AppRouteMatch, AppRouter, ErrorEntry, LayoutEntry, LoadingEntry, NotFoundEntry,
 };
-pub use routes_manifest::RoutesManifest;
+pub use routes_manifest::{ResolvedRoute, RouteRenderMode, RoutesManifest};
 pub use types::{RouteSegment, RouteSegmentType};
@@ -4,9 +4,11 @@ use tokio::fs;
 
 use super::{
     api_routes::{ApiRouteEntry, ApiRouteManifest},
-    app_router::AppRouteManifest,
+    app_router::{AppRouteManifest, AppRouter},
 };
-use crate::server::og::OgImageEntry;
+use crate::server::{config::Config, og::OgImageEntry};
+
+const DEFAULT_PAGE_CONTENT_TYPE: &str = "text/html; charset=utf-8";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[non_exhaustive]
@@ -19,6 +21,35 @@ pub struct RoutesManifest {
     pub og_images: Vec<OgImageEntry>,
 }
 
+/// How a route's response body is produced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RouteRenderMode {
+    /// Shell first, then Suspense boundaries as they resolve.
+    Stream,
+    /// Rendered in full per request before the response is sent.
+    Buffer,
+    /// No dynamic segments and cacheable, so it can be served from a CDN.
+    Static,
+}
+
+/// One discovered route with the policies `Config` resolves for it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct ResolvedRoute {
+    pub path: String,
+    #[serde(rename = "componentId", skip_serializing_if = "Option::is_none")]
+    pub component_id: Option<String>,
+    #[serde(rename = "renderMode")]
+    pub render_mode: RouteRenderMode,
+    #[serde(rename = "cacheControl")]
+    pub cache_control: String,
+    #[serde(rename = "contentType", skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    #[serde(rename = "isApi")]
+    pub is_api: bool,
+}
+
 impl RoutesManifest {
     #[expect(clippy::missing_errors_doc)]
     pub async fn load_from_file(path: &str) -> Result<Self, RariError> {
@@ -33,6 +64,59 @@ impl RoutesManifest {
     pub fn api_manifest(&self) -> ApiRouteManifest {
         ApiRouteManifest { api_routes: self.api_routes.clone() }
     }
+
+    /// Every page and API route with the render mode, cache policy and content
+    /// type `config` gives it, sorted by path. Build tools emit this for CDN and
+    /// deployment configuration.
+    pub fn resolve_routes(&self, config: &Config) -> Vec<ResolvedRoute> {
+        let router = AppRouter::new(self.app.clone());
+
+        let pages = self.app.routes.iter().map(|route| {
+            let cache_control = config.cache_control_for_route(&route.path);
+            let render_mode = if router.find_loading(&route.path).is_some()
+                && config.should_stream(&route.path)
+            {
+                RouteRenderMode::Stream
+            } else if route.is_dynamic || cache_control.no_store || cache_control.no_cache {
+                RouteRenderMode::Buffer
+            } else {
+                RouteRenderMode::Static
+            };
+
+            ResolvedRoute {
+                path: route.path.clone(),
+                component_id: route.component_id.clone(),
+                render_mode,
+                cache_control: cache_control.to_string(),
+                content_type: Some(
+                    config
+                        .content_type_for_route(&route.path)
+                        .unwrap_or(DEFAULT_PAGE_CONTENT_TYPE)
+                        .to_string(),
+                ),
+                is_api: config.is_api_route(&route.path),
+            }
+        });
+
+        let api = self.api_routes.iter().map(|route| ResolvedRoute {
+            path: route.path.clone(),
+            component_id: route.component_id.clone(),
+            render_mode: RouteRenderMode::Buffer,
+            cache_control: config.get_cache_control_for_route(&route.path).to_string(),
+            content_type: config.content_type_for_route(&route.path).map(str::to_string),
+            is_api: true,
+        });
+
+        let mut routes: Vec<_> = pages.chain(api).collect();
+        routes.sort_by(|a, b| a.path.cmp(&b.path));
+        routes
+    }
+
+    #[expect(clippy::missing_errors_doc)]
+    pub fn resolved_routes_json(&self, config: &Config) -> Result<String, RariError> {
+        serde_json::to_string_pretty(&self.resolve_routes(config))
+            .map_err(|e| RariError::serialization(format!("Failed to serialize routes: {e}")))
+    }
 }
 
 #[cfg(test)]
@@ -60,4 +144,58 @@ mod tests {
         assert_eq!(manifest.og_images.len(), 1);
         assert_eq!(manifest.api_manifest().api_routes.len(), 1);
     }
+
+    #[test]
+    fn resolves_route_policies_from_config() {
+        let json = r#"{
+            "routes": [
+                {"path": "/", "filePath": "page.tsx", "segments": [], "params": [], "isDynamic": false, "componentId": "app/page"},
+                {"path": "/blog/[slug]", "filePath": "blog/[slug]/page.tsx", "segments": [], "params": ["slug"], "isDynamic": true},
+                {"path": "/dashboard", "filePath": "dashboard/page.tsx", "segments": [], "params": [], "isDynamic": false},
+                {"path": "/feed.xml", "filePath": "feed.xml/page.tsx", "segments": [], "params": [], "isDynamic": false}
+            ],
+            "layouts": [],
+            "loading": [{"path": "/dashboard", "filePath": "dashboard/loading.tsx"}],
+            "errors": [],
+            "notFound": [],
+            "generated": "2026-01-01",
+            "apiRoutes": [{"path": "/api/hello", "filePath": "api/hello/route.ts", "methods": ["GET"], "segments": [], "params": [], "isDynamic": false}]
+        }"#;
+        #[expect(clippy::expect_used)]
+        let manifest: RoutesManifest = serde_json::from_str(json).expect("manifest should parse");
+
+        let mut config = Config::new(crate::server::config::Mode::Production);
+        config.caching.routes.insert("/".to_string(), "public, max-age=60".to_string());
+        config.caching.routes.insert("/feed.xml".to_string(), "public, max-age=300".to_string());
+        config.caching.routes.insert("/api/*".to_string(), "no-store".to_string());
+        config
+            .content_type
+            .routes
+            .insert("/feed.xml".to_string(), "application/rss+xml".to_string());
+
+        let routes = manifest.resolve_routes(&config);
+        let by_path = |path: &str| routes.iter().find(|route| route.path == path).cloned();
+
+        let home = by_path("/");
+        assert_eq!(home.as_ref().map(|r| r.render_mode), Some(RouteRenderMode::Static));
+        assert_eq!(home.as_ref().map(|r| r.cache_control.as_str()), Some("public, max-age=60"));
+        assert_eq!(home.and_then(|r| r.component_id), Some("app/page".to_string()));
+
+        assert_eq!(by_path("/blog/[slug]").map(|r| r.render_mode), Some(RouteRenderMode::Buffer));
+        assert_eq!(by_path("/dashboard").map(|r| r.render_mode), Some(RouteRenderMode::Stream));
+        assert_eq!(
+            by_path("/feed.xml").and_then(|r| r.content_type),
+            Some("application/rss+xml".to_string())
+        );
+
+        let api = by_path("/api/hello");
+        assert!(api.as_ref().is_some_and(|r| r.is_api));
+        assert_eq!(api.map(|r| r.cache_control), Some("no-store".to_string()));
+
+        assert!(
+            manifest
+                .resolved_routes_json(&config)
+                .is_ok_and(|json| json.contains("\"renderMode\": \"stream\""))
+        );
+    }
 }
@@ -1,5 +1,7 @@
 #![expect(clippy::missing_errors_doc)]
 
+use std::path::{Path as FsPath, PathBuf};
+
 use axum::{
     body::Body,
     extract::{Path, State},
@@ -11,7 +13,7 @@ use tokio::fs;
 
 use crate::server::{
     ServerState,
-    config::Config,
+    config::{Config, StaticConfig},
     core::utils::{http::get_content_type, path_validation::validate_safe_path},
     error_response::HttpError,
 };
@@ -20,6 +22,21 @@ fn static_dev() -> bool {
     Config::get().is_some_and(Config::is_development)
 }
 
+/// Resolve `path` under `root` to a regular file the static config allows.
+pub(crate) async fn resolve_public_file(
+    static_files: &StaticConfig,
+    root: &FsPath,
+    path: &str,
+) -> Option<PathBuf> {
+    if !static_files.is_servable(path) {
+        return None;
+    }
+
+    let file_path = validate_safe_path(root, path).await.ok()?;
+    let metadata = fs::metadata(&file_path).await.ok()?;
+    metadata.is_file().then_some(file_path)
+}
+
 pub async fn root_handler(State(_state): State<ServerState>) -> Result<Response, HttpError> {
     let Some(config) = Config::get() else {
         tracing::error!("Failed to get global configuration for root_handler");
@@ -78,12 +95,8 @@ pub async fn static_or_spa_handler(
         ));
     };
 
-    let Ok(file_path) = validate_safe_path(config.public_dir(), &path).await else {
-        return Ok(StatusCode::NOT_FOUND.into_response());
-    };
-
-    if let Ok(metadata) = fs::metadata(&file_path).await
-        && metadata.is_file()
+    if let Some(file_path) =
+        resolve_public_file(&config.static_files, config.public_dir(), &path).await
     {
         match fs::read(&file_path).await {
             Ok(content) => {
@@ -167,18 +180,12 @@ pub async fn serve_static_asset(
 
     let assets_dir = state.config.public_dir().join("assets");
 
-    let Ok(file_path) = validate_safe_path(&assets_dir, &asset_path).await else {
-        return Ok(StatusCode::NOT_FOUND.into_response());
-    };
-
-    let Ok(metadata) = fs::metadata(&file_path).await else {
+    let Some(file_path) =
+        resolve_public_file(&state.config.static_files, &assets_dir, &asset_path).await
+    else {
         return Ok(StatusCode::NOT_FOUND.into_response());
     };
 
-    if !metadata.is_file() {
-        return Ok(StatusCode::NOT_FOUND.into_response());
-    }
-
     match fs::read(&file_path).await {
         Ok(content) => {
             let content_type = get_content_type(&asset_path);
@@ -231,3 +238,34 @@ pub fn cors_preflight_response() -> Response {
 pub async fn cors_preflight_ok() -> Response {
     cors_preflight_response()
 }
+
+#[cfg(test)]
+#[expect(clippy::expect_used)]
+mod tests {
+    use std::{
+        env, process,
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_resolve_public_file_skips_env_but_serves_js() {
+        let public_dir = env::temp_dir().join(format!(
+            "rari-static-ext-{}-{}",
+            process::id(),
+            SystemTime::now().duration_since(UNIX_EPOCH).expect("time").as_nanos()
+        ));
+        std::fs::create_dir_all(&public_dir).expect("temp public dir");
+        std::fs::write(public_dir.join(".env"), "SECRET=1").expect(".env");
+        std::fs::write(public_dir.join("app.js"), "console.log(1)").expect("app.js");
+        std::fs::write(public_dir.join("app.js.map"), "{}").expect("app.js.map");
+
+        let static_files = StaticConfig::default();
+        assert!(resolve_public_file(&static_files, &public_dir, "app.js").await.is_some());
+        assert!(resolve_public_file(&static_files, &public_dir, ".env").await.is_none());
+        assert!(resolve_public_file(&static_files, &public_dir, "app.js.map").await.is_none());
+
+        let _ = std::fs::remove_dir_all(&public_dir);
+    }
+}
@@ -78,21 +78,36 @@ pub async fn handle_hmr_action(
     State(state): State<ServerState>,
     Json(request): Json<HmrRequest>,
 ) -> Result<Json<Value>, StatusCode> {
-    match request {
-        HmrRequest::Register { file_path } => handle_register(state, file_path).await,
+    let cancellation = Arc::clone(&state.hmr_render_cancellation);
+
+    let (changed, result) = match request {
+        HmrRequest::Register { file_path } => return handle_register(state, file_path).await,
         HmrRequest::Invalidate { component_id, file_path } => {
-            handle_invalidate(state, component_id, file_path).await
+            (component_id.clone(), handle_invalidate(state, component_id, file_path).await)
         }
         HmrRequest::Reload { component_id, file_path } => {
-            handle_reload(state, component_id, file_path).await
+            (component_id.clone(), handle_reload(state, component_id, file_path).await)
         }
         HmrRequest::InvalidateApiRoute { file_path } => {
-            Ok(handle_invalidate_api_route(&state, &file_path))
+            return Ok(handle_invalidate_api_route(&state, &file_path));
         }
         HmrRequest::ReloadComponent { component_id, bundle_path } => {
-            handle_reload_component(state, component_id, bundle_path).await
+            (component_id.clone(), handle_reload_component(state, component_id, bundle_path).await)
         }
+    };
+
+    // Renders of routes using the changed module were started against the old
+    // one; close them now that the fresh one is loaded so the client re-requests.
+    let cancelled = cancellation.cancel_active(&changed);
+    if cancelled > 0 {
+        tracing::info!(
+            component_id = %changed,
+            "Cancelled {} in-flight render(s) after module change",
+            cancelled
+        );
     }
+
+    result
 }
 
 async fn invalidate_component_cache(cache: &ResponseCache, component_id: &str) {
@@ -2,7 +2,11 @@
 
 use std::{path::Path, sync::Arc};
 
-use axum::{extract::State, http::StatusCode, response::Json};
+use axum::{
+    extract::State,
+    http::{StatusCode, header},
+    response::{IntoResponse, Json, Response},
+};
 use cow_utils::CowUtils;
 use rari_error::RariError;
 use serde_json::Value;
@@ -453,3 +457,12 @@ pub async fn health_check() -> Result<Json<Value>, StatusCode> {
         "service": "rari-rsc-server"
     })))
 }
+
+pub async fn config_dump(State(state): State<ServerState>) -> Result<Response, StatusCode> {
+    let json = state.config.to_redacted_json_string().map_err(|e| {
+        tracing::error!("Failed to serialize config: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(([(header::CONTENT_TYPE, "application/json")], json).into_response())
+}
@@ -123,6 +123,12 @@ pub enum RariError {
     JsRuntime(String, Option<Box<ErrorMetadata>>),
     IoError(String, Option<Box<ErrorMetadata>>),
     Cache(String, Option<Box<ErrorMetadata>>),
+    /// Not a failure: a render asked to redirect to `location` with a 3xx `status`.
+    Redirect {
+        location: String,
+        status: u16,
+        metadata: Option<Box<ErrorMetadata>>,
+    },
 }
 
 impl Display for RariError {
@@ -143,6 +149,7 @@ impl Display for RariError {
             Self::JsRuntime(msg, _) => write!(f, "JavaScript runtime error: {msg}"),
             Self::IoError(msg, _) => write!(f, "I/O error: {msg}"),
             Self::Cache(msg, _) => write!(f, "Cache error: {msg}"),
+            Self::Redirect { location, .. } => write!(f, "Redirect to {location}"),
         }
     }
 }
@@ -172,7 +179,8 @@ impl RariError {
             | Self::JsExecution(msg, _)
             | Self::JsRuntime(msg, _)
             | Self::IoError(msg, _)
-            | Self::Cache(msg, _) => msg.clone(),
+            | Self::Cache(msg, _)
+            | Self::Redirect { location: msg, .. } => msg.clone(),
         }
     }
 
@@ -193,6 +201,7 @@ impl RariError {
             Self::JsRuntime(_, _) => "JS_RUNTIME_ERROR",
             Self::IoError(_, _) => "IO_ERROR",
             Self::Cache(_, _) => "CACHE_ERROR",
+            Self::Redirect { .. } => "REDIRECT",
         }
     }
 
@@ -212,6 +221,7 @@ impl RariError {
             | Self::JsRuntime(_, meta)
             | Self::IoError(_, meta)
             | Self::Cache(_, meta)
+            | Self::Redirect { metadata: meta, .. }
             | Self::Forbidden(_, meta) => meta.as_deref(),
         }
     }
@@ -232,6 +242,7 @@ impl RariError {
             | Self::JsRuntime(_, meta)
             | Self::IoError(_, meta)
             | Self::Cache(_, meta)
+            | Self::Redirect { metadata: meta, .. }
             | Self::Forbidden(_, meta) => meta,
         }
     }
@@ -313,6 +324,21 @@ impl RariError {
         Self::ServerError(message.into(), None)
     }
 
+    /// A render-time redirect to `location` with a 3xx `status`; anything
+    /// outside 300..=399 falls back to 307.
+    pub fn redirect(location: impl Into<String>, status: u16) -> Self {
+        let status = if (300..=399).contains(&status) { status } else { 307 };
+        Self::Redirect { location: location.into(), status, metadata: None }
+    }
+
+    /// Location and status when this is a [`RariError::Redirect`].
+    pub fn redirect_target(&self) -> Option<(&str, u16)> {
+        match self {
+            Self::Redirect { location, status, .. } => Some((location, *status)),
+            _ => None,
+        }
+    }
+
     #[must_use]
     pub fn with_source(mut self, source: Box<dyn error::Error + Send + Sync>) -> Self {
         let code = self.code().to_string();
@@ -590,6 +616,7 @@ impl RariError {
     pub fn status_code(&self) -> u16 {
         match self {
             Self::NotFound(_, _) => 404,
+            Self::Redirect { status, .. } => *status,
             Self::Validation(_, _) | Self::BadRequest(_, _) | Self::Deserialization(_, _) => 400,
             Self::Forbidden(_, _) => 403,
             Self::Timeout(_, _) => 408,
@@ -611,6 +638,7 @@ impl RariError {
         } else {
             match self {
                 Self::NotFound(_, _) => "Resource not found".to_string(),
+                Self::Redirect { .. } => "Redirect".to_string(),
                 Self::Validation(_, _) => "Validation failed".to_string(),
                 Self::BadRequest(_, _) => "Bad request".to_string(),
                 Self::Forbidden(_, _) => "Access forbidden".to_string(),
@@ -666,6 +694,17 @@ mod tests {
         assert_eq!(RariError::server_error("test").status_code(), 500);
     }
 
+    #[test]
+    fn test_redirect_carries_location_and_status() {
+        let error = RariError::redirect("/login", 303);
+        assert_eq!(error.redirect_target(), Some(("/login", 303)));
+        assert_eq!(error.status_code(), 303);
+        assert_eq!(error.code(), "REDIRECT");
+
+        assert_eq!(RariError::redirect("/login", 200).status_code(), 307);
+        assert_eq!(RariError::internal("/login").redirect_target(), None);
+    }
+
     #[test]
     fn test_safe_message_development() {
         let error = RariError::internal("Detailed internal error with stack trace");
@@ -4,7 +4,11 @@ import type { ModuleAnalysis } from './analysis/directives'
 import type { MdxPluginOptions } from './mdx/registry'
 import type { RariPlugin } from './plugin/types'
 import type { ServerBuildOptions } from './server/build'
-import type { ServerCacheConfig, ServerCacheLayerConfig } from './server/config'
+import type {
+  ServerCacheConfig,
+  ServerCacheLayerConfig,
+  ServerCSPRouteOverride,
+} from './server/config'
 import type { ProxyPluginOptions } from '@/proxy/build/vite-plugin'
 import { Buffer } from 'node:buffer'
 import { spawn, spawnSync } from 'node:child_process'
@@ -198,10 +202,14 @@ export interface RariOptions {
     readonly connectSrc?: readonly string[]
     readonly defaultSrc?: readonly string[]
     readonly workerSrc?: readonly string[]
+    readonly frameSrc?: readonly string[]
     readonly frameAncestors?: readonly string[]
     readonly baseUri?: readonly string[]
     readonly formAction?: readonly string[]
     readonly useNonces?: boolean
+    readonly autoUnsafeInline?: boolean
+    readonly allowEval?: boolean
+    readonly routes?: Readonly<Record<string, ServerCSPRouteOverride>>
   }
   readonly cacheControl?: {
     readonly routes: Readonly<Record<string, string>>
@@ -6,12 +6,30 @@ export interface ServerCSPConfig {
   readonly connectSrc?: readonly string[]
   readonly defaultSrc?: readonly string[]
   readonly workerSrc?: readonly string[]
+  readonly frameSrc?: readonly string[]
   readonly frameAncestors?: readonly string[]
   readonly baseUri?: readonly string[]
   readonly formAction?: readonly string[]
   readonly useNonces?: boolean
+  readonly autoUnsafeInline?: boolean
+  readonly allowEval?: boolean
+  readonly routes?: Readonly<Record<string, ServerCSPRouteOverride>>
 }
 
+export type ServerCSPRouteOverride = Pick<
+  ServerCSPConfig,
+  | 'scriptSrc'
+  | 'styleSrc'
+  | 'imgSrc'
+  | 'fontSrc'
+  | 'connectSrc'
+  | 'defaultSrc'
+  | 'workerSrc'
+  | 'frameSrc'
+  | 'frameAncestors'
+  | 'formAction'
+>
+
 export interface ServerCacheControlConfig {
   readonly routes: Readonly<Record<string, string>>
 }